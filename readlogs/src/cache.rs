@@ -0,0 +1,349 @@
+use std::cmp::Reverse;
+
+use anyhow::{anyhow, ensure, Context};
+use chrono::prelude::*;
+use js_sys::{Array, Promise, Uint8Array};
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::{closure::Closure, JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{window, IdbDatabase, IdbObjectStore, IdbRequest, IdbTransactionMode};
+
+use crate::RemoteObject;
+
+const DATABASE_NAME: &str = "readlogs";
+const DATABASE_VERSION: u32 = 1;
+/// Object store with a JSON-serialized `RecentLog` per key.
+const METADATA_STORE: &str = "metadata";
+/// Object store with the fetched payload (text or `zip` bytes) per key.
+const PAYLOADS_STORE: &str = "payloads";
+
+/// Payloads larger than this (in bytes) aren't cached.
+pub const MAX_LOG_SIZE: usize = 64 * 1024 * 1024;
+/// Once the payloads would take up more than this (in bytes), the least recently fetched
+/// ones are removed from the cache.
+pub const MAX_CACHE_SIZE: usize = 256 * 1024 * 1024;
+
+/// A debug log whose payload is cached, so that it can be reopened without fetching it again.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecentLog {
+    /// `RemoteObject::key`, which the log is cached under.
+    pub key: String,
+    /// debuglogs.org URL of the log.
+    pub url: String,
+    pub label: String,
+    /// Milliseconds since the Unix epoch.
+    pub fetched_at: i64,
+    /// Size of the cached payload, in bytes.
+    pub size: usize,
+}
+
+impl RecentLog {
+    pub fn remote_object(&self) -> Option<RemoteObject> {
+        self.url.parse().ok()
+    }
+
+    pub fn fetched_at(&self) -> Option<DateTime<Utc>> {
+        Utc.timestamp_millis_opt(self.fetched_at).single()
+    }
+}
+
+/// Formats the size using the largest fitting unit, e.g. `1.5 MB`.
+pub fn format_size(bytes: usize) -> String {
+    const UNITS: [&str; 3] = ["KB", "MB", "GB"];
+
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = UNITS[0];
+
+    for next_unit in &UNITS[1..] {
+        if size < 1024.0 {
+            break;
+        }
+
+        size /= 1024.0;
+        unit = next_unit;
+    }
+
+    format!("{size:.1} {unit}")
+}
+
+/// Keys of the logs that have to be removed for a payload of the given size to fit,
+/// keeping the most recently fetched ones.
+fn evicted(logs: &[RecentLog], key: &str, size: usize, max_size: usize) -> Vec<String> {
+    let mut logs = logs.iter().filter(|log| log.key != key).collect::<Vec<_>>();
+    logs.sort_by_key(|log| Reverse(log.fetched_at));
+
+    let mut total_size = size;
+
+    logs.into_iter()
+        .filter(|log| {
+            total_size += log.size;
+            total_size > max_size
+        })
+        .map(|log| log.key.clone())
+        .collect()
+}
+
+fn js_error(error: JsValue) -> anyhow::Error {
+    anyhow!("{error:?}")
+}
+
+/// Resolves once the request succeeds, with its result.
+///
+/// Only one of the handlers is ever called, so both are kept until the request settles and then
+/// dropped, instead of being leaked.
+async fn request_future(request: &IdbRequest) -> Result<JsValue, JsValue> {
+    let mut handlers = None;
+
+    let promise = Promise::new(&mut |resolve, reject| {
+        let success_request = request.clone();
+        let onsuccess: Closure<dyn FnMut()> = Closure::once(move || {
+            let result = success_request.result().unwrap_or(JsValue::UNDEFINED);
+            let _ = resolve.call1(&JsValue::UNDEFINED, &result);
+        });
+
+        let error_request = request.clone();
+        let onerror: Closure<dyn FnMut()> = Closure::once(move || {
+            let error = match error_request.error() {
+                Ok(Some(error)) => JsValue::from(error),
+                _ => JsValue::UNDEFINED,
+            };
+            let _ = reject.call1(&JsValue::UNDEFINED, &error);
+        });
+
+        request.set_onsuccess(Some(onsuccess.as_ref().unchecked_ref()));
+        request.set_onerror(Some(onerror.as_ref().unchecked_ref()));
+        handlers = Some((onsuccess, onerror));
+    });
+
+    let result = JsFuture::from(promise).await;
+
+    request.set_onsuccess(None);
+    request.set_onerror(None);
+    drop(handlers);
+
+    result
+}
+
+async fn open() -> anyhow::Result<IdbDatabase> {
+    let factory = window()
+        .context("there's no window")?
+        .indexed_db()
+        .map_err(js_error)?
+        .context("IndexedDB isn't available")?;
+
+    let request = factory
+        .open_with_u32(DATABASE_NAME, DATABASE_VERSION)
+        .map_err(js_error)?;
+
+    let upgrade_request = request.clone();
+    let onupgradeneeded: Closure<dyn FnMut()> = Closure::once(move || {
+        if let Ok(database) = upgrade_request
+            .result()
+            .and_then(|result| result.dyn_into::<IdbDatabase>())
+        {
+            for name in [METADATA_STORE, PAYLOADS_STORE] {
+                if !database.object_store_names().contains(name) {
+                    let _ = database.create_object_store(name);
+                }
+            }
+        }
+    });
+    request.set_onupgradeneeded(Some(onupgradeneeded.as_ref().unchecked_ref()));
+
+    let result = request_future(&request).await;
+
+    // The upgrade, if one is needed, happens before the request succeeds.
+    request.set_onupgradeneeded(None);
+    drop(onupgradeneeded);
+
+    result.map_err(js_error)?.dyn_into().map_err(js_error)
+}
+
+fn object_store(
+    database: &IdbDatabase,
+    name: &str,
+    mode: IdbTransactionMode,
+) -> anyhow::Result<IdbObjectStore> {
+    database
+        .transaction_with_str_and_mode(name, mode)
+        .and_then(|transaction| transaction.object_store(name))
+        .map_err(js_error)
+}
+
+async fn put(
+    database: &IdbDatabase,
+    store: &str,
+    key: &str,
+    value: &JsValue,
+) -> anyhow::Result<()> {
+    let request = object_store(database, store, IdbTransactionMode::Readwrite)?
+        .put_with_key(value, &JsValue::from_str(key))
+        .map_err(js_error)?;
+
+    request_future(&request).await.map_err(js_error)?;
+
+    Ok(())
+}
+
+async fn put_metadata(database: &IdbDatabase, log: &RecentLog) -> anyhow::Result<()> {
+    let json = serde_json::to_string(log)?;
+    put(
+        database,
+        METADATA_STORE,
+        &log.key,
+        &JsValue::from_str(&json),
+    )
+    .await
+}
+
+/// Cached logs, most recently fetched first.
+pub async fn recent_logs() -> anyhow::Result<Vec<RecentLog>> {
+    let database = open().await?;
+
+    let request = object_store(&database, METADATA_STORE, IdbTransactionMode::Readonly)?
+        .get_all()
+        .map_err(js_error)?;
+    let values: Array = request_future(&request)
+        .await
+        .map_err(js_error)?
+        .dyn_into()
+        .map_err(js_error)?;
+
+    let mut logs = values
+        .iter()
+        .filter_map(|value| value.as_string())
+        .filter_map(|json| serde_json::from_str::<RecentLog>(&json).ok())
+        .collect::<Vec<_>>();
+    logs.sort_by_key(|log| Reverse(log.fetched_at));
+
+    Ok(logs)
+}
+
+/// The cached payload of the log with the given key, if there is one.
+pub async fn load(key: &str) -> anyhow::Result<Option<Vec<u8>>> {
+    let database = open().await?;
+
+    let request = object_store(&database, PAYLOADS_STORE, IdbTransactionMode::Readonly)?
+        .get(&JsValue::from_str(key))
+        .map_err(js_error)?;
+    let value = request_future(&request).await.map_err(js_error)?;
+
+    Ok((!value.is_undefined()).then(|| Uint8Array::new(&value).to_vec()))
+}
+
+/// Caches the payload of the log, keeping its label if it was cached before.
+pub async fn save(remote_object: &RemoteObject, payload: &[u8]) -> anyhow::Result<()> {
+    ensure!(
+        payload.len() <= MAX_LOG_SIZE,
+        "the debug log is too large to cache ({})",
+        format_size(payload.len())
+    );
+
+    let logs = recent_logs().await?;
+    let key = remote_object.key();
+
+    for key in evicted(&logs, key, payload.len(), MAX_CACHE_SIZE) {
+        remove(&key).await?;
+    }
+
+    let log = RecentLog {
+        key: key.to_owned(),
+        url: remote_object.debuglogs_url(),
+        label: logs
+            .iter()
+            .find(|log| log.key == key)
+            .map(|log| log.label.clone())
+            .unwrap_or_default(),
+        fetched_at: Utc::now().timestamp_millis(),
+        size: payload.len(),
+    };
+
+    let database = open().await?;
+    put(
+        &database,
+        PAYLOADS_STORE,
+        key,
+        &Uint8Array::from(payload).into(),
+    )
+    .await?;
+    put_metadata(&database, &log).await
+}
+
+pub async fn update_label(key: &str, label: String) -> anyhow::Result<()> {
+    let logs = recent_logs().await?;
+
+    match logs.into_iter().find(|log| log.key == key) {
+        Some(log) => put_metadata(&open().await?, &RecentLog { label, ..log }).await,
+        None => Ok(()),
+    }
+}
+
+pub async fn remove(key: &str) -> anyhow::Result<()> {
+    let database = open().await?;
+
+    for store in [METADATA_STORE, PAYLOADS_STORE] {
+        let request = object_store(&database, store, IdbTransactionMode::Readwrite)?
+            .delete(&JsValue::from_str(key))
+            .map_err(js_error)?;
+        request_future(&request).await.map_err(js_error)?;
+    }
+
+    Ok(())
+}
+
+pub async fn clear() -> anyhow::Result<()> {
+    let database = open().await?;
+
+    for store in [METADATA_STORE, PAYLOADS_STORE] {
+        let request = object_store(&database, store, IdbTransactionMode::Readwrite)?
+            .clear()
+            .map_err(js_error)?;
+        request_future(&request).await.map_err(js_error)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+
+    use super::*;
+
+    fn test_log(key: &str, fetched_at: i64, size: usize) -> RecentLog {
+        RecentLog {
+            key: key.to_owned(),
+            url: format!("https://debuglogs.org/{key}"),
+            label: "".to_owned(),
+            fetched_at,
+            size,
+        }
+    }
+
+    #[test_case(0 => "0 B"; "zero")]
+    #[test_case(1023 => "1023 B"; "bytes")]
+    #[test_case(1536 => "1.5 KB"; "kilobytes")]
+    #[test_case(5 * 1024 * 1024 => "5.0 MB"; "megabytes")]
+    #[test_case(3 * 1024 * 1024 * 1024 => "3.0 GB"; "gigabytes")]
+    fn format_size_ok(bytes: usize) -> String {
+        format_size(bytes)
+    }
+
+    #[test_case(10 => Vec::<String>::new(); "fits")]
+    #[test_case(40 => vec!["a".to_owned()]; "oldest")]
+    #[test_case(70 => vec!["c".to_owned(), "a".to_owned()]; "all but newest")]
+    fn evicted_ok(size: usize) -> Vec<String> {
+        let logs = [
+            test_log("a", 1, 30),
+            test_log("b", 3, 30),
+            test_log("c", 2, 30),
+            test_log("d", 4, 100),
+        ];
+
+        evicted(&logs, "d", size, 100)
+    }
+}
@@ -0,0 +1,83 @@
+use std::rc::Rc;
+
+use js_sys::{Function, Reflect};
+use wasm_bindgen::{JsCast, JsValue};
+use web_sys::window;
+use yew::prelude::*;
+
+use crate::components::{Button, ButtonSize};
+
+#[derive(Debug, Clone, PartialEq, Properties)]
+pub struct CopyButtonProps {
+    #[prop_or_default]
+    pub classes: Classes,
+
+    #[prop_or(ButtonSize::Small)]
+    pub size: ButtonSize,
+
+    #[prop_or_default]
+    pub icon: Option<Classes>,
+    pub text: String,
+
+    pub content: Rc<String>,
+}
+
+#[derive(Debug)]
+pub struct CopyButton {
+    /// Whether the current content has been copied, which the button shows.
+    copied: bool,
+}
+
+/// Writes the text to the clipboard with `navigator.clipboard.writeText`, which `web-sys` only
+/// exposes as an unstable API.
+fn write_to_clipboard(text: &str) -> Result<(), JsValue> {
+    let navigator = window().ok_or(JsValue::NULL)?.navigator();
+    let clipboard = Reflect::get(&navigator, &JsValue::from_str("clipboard"))?;
+    let write_text = Reflect::get(&clipboard, &JsValue::from_str("writeText"))?;
+
+    write_text
+        .dyn_into::<Function>()?
+        .call1(&clipboard, &JsValue::from_str(text))?;
+
+    Ok(())
+}
+
+impl Component for CopyButton {
+    type Message = ();
+    type Properties = CopyButtonProps;
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        Self { copied: false }
+    }
+
+    fn changed(&mut self, ctx: &Context<Self>, old_props: &Self::Properties) -> bool {
+        if ctx.props().content != old_props.content {
+            self.copied = false;
+        }
+
+        true
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, _msg: Self::Message) -> bool {
+        self.copied = write_to_clipboard(&ctx.props().content).is_ok();
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let (icon, text) = if self.copied {
+            (Some(classes!("fas", "fa-check")), "Copied".to_owned())
+        } else {
+            (ctx.props().icon.clone(), ctx.props().text.clone())
+        };
+
+        html! {
+            <Button
+                classes={ctx.props().classes.clone()}
+                size={ctx.props().size}
+                {icon}
+                {text}
+                on_click={ctx.link().callback(|_| ())}
+            />
+        }
+    }
+}
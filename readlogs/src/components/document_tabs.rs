@@ -0,0 +1,76 @@
+use yew::prelude::*;
+
+use crate::{
+    components::{Button, ButtonSize},
+    SearchSummary,
+};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct DocumentTabsEntry {
+    pub label: String,
+    pub loading: bool,
+    pub failed: bool,
+    /// Results of the document's search query, if it has one.
+    pub search_summary: Option<SearchSummary>,
+}
+
+#[derive(Debug, Clone, PartialEq, Properties)]
+pub struct DocumentTabsProps {
+    #[prop_or_default]
+    pub classes: Classes,
+
+    pub documents: Vec<DocumentTabsEntry>,
+    pub active: usize,
+    pub on_select: Callback<usize>,
+    pub on_close: Callback<usize>,
+    pub on_new: Callback<()>,
+}
+
+#[function_component(DocumentTabs)]
+pub fn document_tabs(props: &DocumentTabsProps) -> Html {
+    html! {
+        <div class={classes!(props.classes.clone(), "flex", "flex-wrap", "gap-2")}>
+            {
+                for props.documents.iter().enumerate().map(|(index, document)| {
+                    let icon = if document.loading {
+                        classes!("fas", "fa-spinner", "fa-spin")
+                    } else if document.failed {
+                        classes!("fas", "fa-exclamation-triangle")
+                    } else {
+                        classes!("fas", "fa-file-alt")
+                    };
+
+                    let text = match document.search_summary {
+                        Some(SearchSummary { entries: 1, .. }) => format!("{} · 1 entry", document.label),
+                        Some(summary) => format!("{} · {} entries", document.label, summary.entries),
+                        None => document.label.clone(),
+                    };
+
+                    html! {
+                        <div class="flex">
+                            <Button
+                                size={ButtonSize::Small}
+                                active={index == props.active}
+                                {icon}
+                                {text}
+                                on_click={props.on_select.reform(move |_| index)}
+                            />
+                            <Button
+                                size={ButtonSize::Small}
+                                icon={classes!("fas", "fa-times")}
+                                on_click={props.on_close.reform(move |_| index)}
+                            />
+                        </div>
+                    }
+                })
+            }
+
+            <Button
+                size={ButtonSize::Small}
+                icon={classes!("fas", "fa-plus")}
+                text="New"
+                on_click={props.on_new.reform(|_| ())}
+            />
+        </div>
+    }
+}
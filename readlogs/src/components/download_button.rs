@@ -1,4 +1,4 @@
-use std::{convert::identity, rc::Rc};
+use std::convert::identity;
 
 use percent_encoding::{percent_encode, NON_ALPHANUMERIC};
 use wasm_bindgen::JsCast;
@@ -26,7 +26,8 @@ pub struct DownloadButtonProps {
     pub disabled: bool,
 
     pub filename: String,
-    pub content: Rc<String>,
+    /// Generates the content of the file once the button is clicked, as that can be expensive.
+    pub content: Callback<(), String>,
 }
 
 #[derive(Debug)]
@@ -42,6 +43,7 @@ impl Component for DownloadButton {
 
     fn update(&mut self, ctx: &Context<Self>, _msg: Self::Message) -> bool {
         let document = window().unwrap().document().unwrap();
+        let content = ctx.props().content.emit(());
 
         let element = document.create_element("a").unwrap();
         element
@@ -49,7 +51,7 @@ impl Component for DownloadButton {
                 "href",
                 &format!(
                     "data:text/plain;charset=utf-8,{}",
-                    percent_encode(ctx.props().content.as_bytes(), NON_ALPHANUMERIC)
+                    percent_encode(content.as_bytes(), NON_ALPHANUMERIC)
                 ),
             )
             .unwrap();
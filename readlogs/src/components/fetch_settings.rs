@@ -0,0 +1,121 @@
+use yew::prelude::*;
+
+use crate::{
+    components::{Button, ButtonSize, Input, Message},
+    remote_object::FetchMode,
+    settings::HealthCheck,
+};
+
+#[derive(Debug, Clone, PartialEq, Properties)]
+pub struct FetchSettingsProps {
+    #[prop_or_default]
+    pub classes: Classes,
+
+    pub mode: FetchMode,
+    pub health_check: HealthCheck,
+    pub on_mode_changed: Callback<FetchMode>,
+    pub on_health_check: Callback<()>,
+}
+
+#[function_component(FetchSettings)]
+pub fn fetch_settings(props: &FetchSettingsProps) -> Html {
+    let default_proxy = FetchMode::default().to_string();
+
+    let pending_proxy = {
+        let mode = props.mode.clone();
+        let default_proxy = default_proxy.clone();
+        use_state_eq(move || match mode {
+            FetchMode::Proxy(base) => base,
+            FetchMode::Direct => default_proxy,
+        })
+    };
+    let error = use_state_eq(|| None::<String>);
+
+    let apply_proxy = {
+        let pending_proxy = pending_proxy.clone();
+        let error = error.clone();
+        let on_mode_changed = props.on_mode_changed.clone();
+
+        move || match pending_proxy.parse::<FetchMode>() {
+            Ok(mode) => {
+                error.set(None);
+                on_mode_changed.emit(mode);
+            }
+            Err(e) => error.set(Some(e.to_string())),
+        }
+    };
+
+    let is_direct = props.mode == FetchMode::Direct;
+
+    let status = match &props.health_check {
+        HealthCheck::NotChecked => html! {},
+        HealthCheck::Checking => html! { <span class="animate-pulse">{ "Checking..." }</span> },
+        HealthCheck::Ok => html! { <span>{ "✓ Reachable" }</span> },
+        HealthCheck::Failed(reason) => html! {
+            <span class="text-red-600 dark:text-red-400">{ format!("✗ {reason}") }</span>
+        },
+    };
+
+    html! {
+        <Message classes={props.classes.clone()}>
+            <details>
+                <summary class="cursor-pointer font-bold">{ "Fetch settings" }</summary>
+
+                <p>
+                    { "Debug logs are fetched through a proxy, as debuglogs.org doesn't allow other websites to fetch them. " }
+                    { "Use your own deployment of the worker, or fetch directly if your browser allows it. " }
+                    { "This can also be set with the " }<code>{ "?proxy=" }</code>{ " query parameter (a URL or " }<code>{ "direct" }</code>{ ")." }
+                </p>
+
+                <div class="flex flex-wrap items-center gap-2 not-prose text-sm">
+                    <Button
+                        size={ButtonSize::Small}
+                        text="Through a proxy"
+                        active={!is_direct}
+                        on_click={{
+                            let apply_proxy = apply_proxy.clone();
+                            Callback::from(move |_| apply_proxy())
+                        }}
+                    />
+                    <Button
+                        size={ButtonSize::Small}
+                        text="Directly"
+                        active={is_direct}
+                        on_click={props.on_mode_changed.reform(|_| FetchMode::Direct)}
+                    />
+                </div>
+
+                <div class="flex items-center gap-x-2 mt-2 not-prose text-sm">
+                    <Input
+                        classes={classes!("rounded-2xl", "py-1")}
+                        value={(*pending_proxy).clone()}
+                        on_change={{
+                            let pending_proxy = pending_proxy.clone();
+                            Callback::from(move |value| pending_proxy.set(value))
+                        }}
+                        on_submit_maybe={Callback::from(move |actually: bool| if actually { apply_proxy() })}
+                        placeholder={default_proxy}
+                        disabled={is_direct}
+                    />
+                    <Button
+                        size={ButtonSize::Small}
+                        icon={classes!("fas", "fa-heartbeat")}
+                        text="Check"
+                        disabled={props.health_check == HealthCheck::Checking}
+                        on_click={props.on_health_check.reform(|_| ())}
+                    />
+                    { status }
+                </div>
+
+                {
+                    match &*error {
+                        Some(error) => html! {
+                            <p class="text-red-600 dark:text-red-400">{ error }</p>
+                        },
+                        None => html! {},
+                    }
+                }
+            </details>
+        </Message>
+    }
+}
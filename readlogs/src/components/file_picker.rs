@@ -1,49 +1,129 @@
-use std::rc::Rc;
-
 use yew::prelude::*;
 
 use crate::{
     components::{Icon, Message, Table, TableItem, TableRow},
-    parsers::{AppId, LogFilename},
+    parsers::{AppId, ArchiveFilename},
+    FileStatus, SearchSummary,
 };
 
+#[derive(Debug, Clone, PartialEq)]
+pub struct FilePickerEntry {
+    pub name: ArchiveFilename,
+    pub status: FileStatus,
+    pub search_summary: SearchSummary,
+}
+
 #[derive(Debug, Clone, PartialEq, Properties)]
 pub struct FilePickerProps {
     #[prop_or_default]
     pub classes: Classes,
 
-    pub files: Vec<Rc<LogFilename>>,
-    pub selected_file: Rc<LogFilename>,
-    pub on_file_selected: Callback<Rc<LogFilename>>,
+    pub files: Vec<FilePickerEntry>,
+    pub selected_file: ArchiveFilename,
+    pub on_file_selected: Callback<ArchiveFilename>,
 }
 
 #[function_component(FilePicker)]
 pub fn file_picker(props: &FilePickerProps) -> Html {
-    let submission_time = props.files[0].submission_time;
+    let heading = props
+        .files
+        .iter()
+        .find_map(|file| match &file.name {
+            ArchiveFilename::Log(name) => Some(format!("{} AM/PM", name.submission_time)),
+            ArchiveFilename::Other(_) => None,
+        })
+        .unwrap_or_else(|| "Files".to_owned());
+
+    let (logs, others): (Vec<_>, Vec<_>) = props
+        .files
+        .iter()
+        .partition(|file| matches!(file.name, ArchiveFilename::Log(_)));
+
+    let others = if others.is_empty() {
+        html! {}
+    } else {
+        html! {
+            <>
+                <p class="font-bold">{ "Other files" }</p>
+                <Table classes={classes!("font-mono")}>
+                    <tbody>
+                        { for others.into_iter().map(|file| view_file_row(props, file)) }
+                    </tbody>
+                </Table>
+            </>
+        }
+    };
 
     html! {
         <Message
             classes={props.classes.clone()}
-            heading={format!("{submission_time} AM/PM")}
+            {heading}
         >
             <Table classes={classes!("font-mono")}>
                 <tbody>
-                    { for props.files.iter().map(|file| view_file_row(props, Rc::clone(file))) }
+                    { for logs.into_iter().map(|file| view_file_row(props, file)) }
                 </tbody>
             </Table>
+
+            { others }
         </Message>
     }
 }
 
-fn view_file_row(props: &FilePickerProps, file: Rc<LogFilename>) -> Html {
-    let active = props.selected_file == file;
-    let app_id = file.app_id;
-    let file_time = file.file_time.to_string();
+fn view_file_row(props: &FilePickerProps, file: &FilePickerEntry) -> Html {
+    let active = props.selected_file == file.name;
+
+    let (icon, columns) = match &file.name {
+        ArchiveFilename::Log(name) => {
+            let icon = match name.app_id {
+                AppId::Signal => "fa-square",
+                AppId::NotificationServiceExtension => "fa-bell",
+                AppId::ShareAppExtension => "fa-share",
+                AppId::Other(_) => "fa-puzzle-piece",
+            };
 
-    let icon = match app_id {
-        AppId::Signal => "fa-square",
-        AppId::NotificationServiceExtension => "fa-bell",
-        AppId::ShareAppExtension => "fa-share",
+            let (status_icon, status_classes, status_title) = match file.status {
+                FileStatus::Ok => ("fa-check", classes!(), "Parsed successfully"),
+                FileStatus::Partial => (
+                    "fa-exclamation-triangle",
+                    classes!("text-orange-600", "dark:text-orange-400"),
+                    "Parsed with warnings",
+                ),
+                FileStatus::Failed => (
+                    "fa-times",
+                    classes!("text-red-600", "dark:text-red-400"),
+                    "Failed to parse",
+                ),
+            };
+
+            let columns = html! {
+                <>
+                    <TableItem>{ &name.app_id }</TableItem>
+                    <TableItem>{ name.file_time }</TableItem>
+                    <TableItem classes={file.search_summary.max_level.map(|level| level.color()).unwrap_or_default()}>
+                        {
+                            match file.search_summary.entries {
+                                1 => "1 entry".to_owned(),
+                                entries => format!("{entries} entries"),
+                            }
+                        }
+                    </TableItem>
+                    <TableItem classes={status_classes}>
+                        <span title={status_title}><Icon icon={classes!("fas", status_icon)} /></span>
+                    </TableItem>
+                </>
+            };
+
+            (icon, columns)
+        }
+        ArchiveFilename::Other(name) => (
+            if name.ends_with('/') {
+                "fa-folder"
+            } else {
+                "fa-file"
+            },
+            html! { <TableItem colspan={4}>{ name }</TableItem> },
+        ),
     };
 
     let mut classes = classes!(
@@ -65,14 +145,15 @@ fn view_file_row(props: &FilePickerProps, file: Rc<LogFilename>) -> Html {
         classes!()
     });
 
+    let file = file.name.clone();
+
     html! {
         <TableRow
             {classes}
-            on_click={props.on_file_selected.clone().reform(move |_| Rc::clone(&file))}
+            on_click={props.on_file_selected.reform(move |_| file.clone())}
         >
             <TableItem><Icon icon={classes!("fas", icon)} /></TableItem>
-            <TableItem>{ app_id }</TableItem>
-            <TableItem>{ file_time }</TableItem>
+            { columns }
         </TableRow>
     }
 }
@@ -0,0 +1,77 @@
+use yew::prelude::*;
+
+use crate::{
+    components::{Button, ButtonSize, Icon, Message, Table, TableItem, TableRow},
+    settings, RemoteObject,
+};
+
+#[derive(Debug, Clone, PartialEq, Properties)]
+pub struct FoundLogsProps {
+    #[prop_or_default]
+    pub classes: Classes,
+
+    pub remote_objects: Vec<RemoteObject>,
+    /// Called with the debuglogs.org URL of the log.
+    pub on_open: Callback<String>,
+    pub on_open_all: Callback<()>,
+    pub on_dismiss: Callback<()>,
+}
+
+#[function_component(FoundLogs)]
+pub fn found_logs(props: &FoundLogsProps) -> Html {
+    html! {
+        <Message
+            classes={props.classes.clone()}
+            heading={format!("Found {} debug logs", props.remote_objects.len())}
+        >
+            <Table classes={classes!("text-sm")}>
+                <tbody>
+                    {
+                        for props.remote_objects.iter().map(|remote_object| {
+                            let url = remote_object.debuglogs_url();
+                            let link = settings::log_link(&url);
+
+                            html! {
+                                <TableRow>
+                                    <TableItem>{ remote_object.platform() }</TableItem>
+                                    <TableItem classes={classes!("font-mono")}>
+                                        { remote_object.version().clone().unwrap_or_default() }
+                                    </TableItem>
+                                    <TableItem classes={classes!("font-mono", "break-all")}>
+                                        { format!("{}…", &remote_object.key()[..8]) }
+                                    </TableItem>
+                                    <TableItem classes={classes!("whitespace-nowrap")}>
+                                        <Button
+                                            size={ButtonSize::Small}
+                                            icon={classes!("fas", "fa-folder-open")}
+                                            text="Open"
+                                            on_click={props.on_open.reform(move |_| url.clone())}
+                                        />
+                                        <a class="ml-2" href={link} target="_blank" title="Open in a new tab">
+                                            <Icon icon={classes!("fas", "fa-external-link-alt")} />
+                                        </a>
+                                    </TableItem>
+                                </TableRow>
+                            }
+                        })
+                    }
+                </tbody>
+            </Table>
+
+            <div class="flex gap-x-2 mt-2">
+                <Button
+                    size={ButtonSize::Small}
+                    icon={classes!("fas", "fa-folder-open")}
+                    text="Open all"
+                    on_click={props.on_open_all.reform(|_| ())}
+                />
+                <Button
+                    size={ButtonSize::Small}
+                    icon={classes!("fas", "fa-times")}
+                    text="Dismiss"
+                    on_click={props.on_dismiss.reform(|_| ())}
+                />
+            </div>
+        </Message>
+    }
+}
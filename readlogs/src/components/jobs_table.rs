@@ -0,0 +1,204 @@
+use std::rc::Rc;
+
+use strum::IntoEnumIterator;
+use yew::prelude::*;
+
+use crate::{
+    components::{
+        Button, ButtonSize, Input, Message, Table, TableItem, TableRow, Title, TitleLevel,
+    },
+    jobs::{self, Job, JobColumn, JobState},
+};
+
+#[derive(Debug, Clone, PartialEq, Properties)]
+pub struct JobsTableProps {
+    #[prop_or_default]
+    pub classes: Classes,
+
+    pub jobs: Rc<Vec<Job>>,
+
+    /// Called with a regular expression matching the id of the selected job.
+    pub on_job_selected: Callback<String>,
+}
+
+fn job_anchor(id: &str) -> String {
+    format!("job-{id}")
+}
+
+#[function_component(JobsTable)]
+pub fn jobs_table(props: &JobsTableProps) -> Html {
+    let sort = use_state_eq(|| (JobColumn::State, true));
+    let filter = use_state_eq(String::new);
+
+    if props.jobs.is_empty() {
+        return html! {
+            <Message text="This file doesn't seem to contain any jobs." />
+        };
+    }
+
+    let (sort_column, ascending) = *sort;
+
+    let mut jobs = props
+        .jobs
+        .iter()
+        .filter(|job| job.contains(&filter))
+        .collect::<Vec<_>>();
+
+    jobs.sort_by(|a, b| {
+        let ordering = a.compare_by(b, sort_column);
+
+        if ascending {
+            ordering
+        } else {
+            ordering.reverse()
+        }
+    });
+
+    let view_header = |column: JobColumn| {
+        let icon = if column != sort_column {
+            "fa-sort"
+        } else if ascending {
+            "fa-sort-up"
+        } else {
+            "fa-sort-down"
+        };
+
+        let sort = sort.clone();
+
+        html! {
+            <TableItem tag="th">
+                <Button
+                    classes={classes!("rounded-2xl", "whitespace-nowrap")}
+                    size={ButtonSize::Small}
+                    icon={classes!("fas", icon)}
+                    text={column.to_string()}
+                    active={column == sort_column}
+                    on_click={Callback::from(move |_| sort.set((column, column != sort_column || !ascending)))}
+                />
+            </TableItem>
+        }
+    };
+
+    html! {
+        <div class={props.classes.clone()}>
+            <div class="flex mb-4 not-prose">
+                <Input
+                    classes={classes!("rounded-2xl")}
+                    value={(*filter).clone()}
+                    on_change={Callback::from({
+                        let filter = filter.clone();
+                        move |value| filter.set(value)
+                    })}
+                    placeholder="Filter jobs..."
+                />
+            </div>
+
+            <Table>
+                <thead>
+                    <TableRow classes={classes!("text-left")}>
+                        { for JobColumn::iter().map(view_header) }
+                    </TableRow>
+                </thead>
+                <tbody class="font-mono">
+                    { for jobs.iter().map(|job| view_job_row(props, job)) }
+                </tbody>
+            </Table>
+
+            { view_dependency_graph(&props.jobs) }
+        </div>
+    }
+}
+
+fn view_job_link(id: &str) -> Html {
+    html! {
+        <a href={format!("#{}", job_anchor(id))} class="whitespace-nowrap">{ id }</a>
+    }
+}
+
+fn view_job_row(props: &JobsTableProps, job: &Job) -> Html {
+    let state_classes = match job.state {
+        JobState::Running => classes!("text-green-600", "dark:text-green-400"),
+        JobState::Retrying => classes!("text-orange-600", "dark:text-orange-400"),
+        JobState::Pending => classes!(),
+    };
+
+    let regex = regex::escape(&job.id);
+
+    html! {
+        <TableRow>
+            <TableItem><span id={job_anchor(&job.id)} class="whitespace-nowrap">{ &job.id }</span></TableItem>
+            <TableItem>{ job.factory_key.clone().unwrap_or_default() }</TableItem>
+            <TableItem>{ job.queue_key.clone().unwrap_or_default() }</TableItem>
+            <TableItem classes={state_classes}>{ job.state }</TableItem>
+            <TableItem>
+                {
+                    match (job.run_attempt, job.max_attempts) {
+                        (Some(attempt), Some(max)) if max > 0 => format!("{attempt} / {max}"),
+                        (Some(attempt), _) => attempt.to_string(),
+                        (None, _) => "".to_owned(),
+                    }
+                }
+            </TableItem>
+            <TableItem classes={classes!("whitespace-nowrap")}>
+                { job.next_run_time.map(|time| time.to_string()).unwrap_or_default() }
+            </TableItem>
+            <TableItem>
+                { for job.dependencies.iter().map(|dependency| html! { <div>{ view_job_link(dependency) }</div> }) }
+            </TableItem>
+            <TableItem>
+                <Button
+                    classes={classes!("rounded-2xl", "whitespace-nowrap")}
+                    size={ButtonSize::Small}
+                    icon={classes!("fas", "fa-search")}
+                    text={job.mentions.to_string()}
+                    disabled={job.mentions == 0}
+                    on_click={props.on_job_selected.reform(move |_| regex.clone())}
+                />
+            </TableItem>
+        </TableRow>
+    }
+}
+
+fn view_dependency_graph(jobs: &[Job]) -> Html {
+    let roots = jobs::dependency_roots(jobs);
+
+    if roots.is_empty() {
+        return html! {};
+    }
+
+    html! {
+        <>
+            <Title level={TitleLevel::H2} text="Dependencies" />
+            <ul class="font-mono">
+                { for roots.into_iter().map(|job| view_dependency_tree(jobs, job, &mut vec![])) }
+            </ul>
+        </>
+    }
+}
+
+/// Renders the job along with the jobs that depend on it (recursively).
+///
+/// `path` contains the ids of the jobs above this one, in order to not loop forever on cycles.
+fn view_dependency_tree<'a>(jobs: &'a [Job], job: &'a Job, path: &mut Vec<&'a str>) -> Html {
+    let dependents = if path.contains(&job.id.as_str()) {
+        html! { <span>{ " (cycle)" }</span> }
+    } else {
+        path.push(&job.id);
+
+        let dependents = jobs::dependents(jobs, &job.id)
+            .map(|dependent| view_dependency_tree(jobs, dependent, path))
+            .collect::<Html>();
+
+        path.pop();
+
+        html! { <ul>{ dependents }</ul> }
+    };
+
+    html! {
+        <li>
+            { view_job_link(&job.id) }
+            { format!(" {}", job.factory_key.as_deref().unwrap_or_default()) }
+            { dependents }
+        </li>
+    }
+}
@@ -0,0 +1,149 @@
+use std::rc::Rc;
+
+use yew::prelude::*;
+
+use crate::{
+    components::{Message, Select},
+    metrics::{self, Metric, Severity, Thresholds, Timings},
+};
+
+/// Options for `Thresholds`, in milliseconds.
+const THRESHOLD_OPTIONS: [u64; 8] = [100, 250, 500, 1000, 2000, 5000, 10000, 30000];
+
+#[derive(Debug, Clone, PartialEq, Properties)]
+pub struct MetricsChartProps {
+    #[prop_or_default]
+    pub classes: Classes,
+
+    pub metrics: Rc<Vec<Metric>>,
+}
+
+#[function_component(MetricsChart)]
+pub fn metrics_chart(props: &MetricsChartProps) -> Html {
+    let thresholds = use_state_eq(Thresholds::default);
+
+    if props.metrics.is_empty() {
+        return html! {
+            <Message text="There are no local metrics." />
+        };
+    }
+
+    // All bars share the same scale, so that the metrics can be compared.
+    let max = props
+        .metrics
+        .iter()
+        .map(Metric::max)
+        .max()
+        .unwrap_or_default();
+
+    let view_threshold_select =
+        |label: &str, selected: u64, update: fn(Thresholds, u64) -> Thresholds| {
+            let thresholds = thresholds.clone();
+
+            html! {
+                <label class="flex items-center gap-x-2">
+                    { label }
+                    <Select
+                        classes={classes!("text-sm")}
+                        options={THRESHOLD_OPTIONS.iter().map(|value| (value.to_string(), metrics::format_milliseconds(*value))).collect::<Vec<_>>()}
+                        selected={selected.to_string()}
+                        on_change={Callback::from(move |value: String| {
+                            if let Ok(value) = value.parse() {
+                                thresholds.set(update(*thresholds, value));
+                            }
+                        })}
+                    />
+                </label>
+            }
+        };
+
+    html! {
+        <div class={props.classes.clone()}>
+            <div class="flex flex-wrap gap-4 mb-4 not-prose text-sm">
+                { view_threshold_select("Warning from", thresholds.warning, Thresholds::with_warning) }
+                { view_threshold_select("Critical from", thresholds.critical, Thresholds::with_critical) }
+            </div>
+
+            { for props.metrics.iter().map(|metric| view_metric(metric, max, &thresholds, 0)) }
+        </div>
+    }
+}
+
+fn view_metric(metric: &Metric, max: u64, thresholds: &Thresholds, depth: usize) -> Html {
+    let title = match metric.count {
+        Some(count) => format!("{} ({count}×)", metric.name),
+        None => metric.name.clone(),
+    };
+
+    let classes = if depth == 0 {
+        classes!("mb-6")
+    } else {
+        classes!("ml-6", "mt-2")
+    };
+
+    html! {
+        <div class={classes}>
+            <div class={classes!("font-mono", if depth == 0 { "font-bold" } else { "" })}>{ title }</div>
+
+            {
+                match &metric.timings {
+                    Timings::Percentiles(percentiles) => html! {
+                        for percentiles.iter().map(|(label, value)| {
+                            view_bar(label, value, max, thresholds.severity(value))
+                        })
+                    },
+                    Timings::Unparsed(pairs) => html! {
+                        for pairs.iter().map(|(key, value)| view_raw_row(key, value))
+                    },
+                }
+            }
+
+            { for metric.splits.iter().map(|split| view_metric(split, max, thresholds, depth + 1)) }
+        </div>
+    }
+}
+
+/// A key-value pair of a metric whose timings aren't numbers, as logged.
+fn view_raw_row(key: &str, value: &str) -> Html {
+    html! {
+        <div class="flex items-center gap-x-2 text-sm font-mono">
+            <span class="w-8 shrink-0">{ key }</span>
+            <span>{ value }</span>
+        </div>
+    }
+}
+
+fn view_bar(label: &str, value: u64, max: u64, severity: Severity) -> Html {
+    let width = if max == 0 {
+        0.0
+    } else {
+        value as f64 / max as f64 * 100.0
+    };
+
+    let bar_classes = match severity {
+        Severity::Normal => classes!(
+            "bg-brand-primary-active",
+            "dark:bg-brand-dark-primary-active"
+        ),
+        Severity::Warning => classes!("bg-orange-400"),
+        Severity::Critical => classes!("bg-red-500"),
+    };
+
+    let text_classes = match severity {
+        Severity::Normal => classes!(),
+        Severity::Warning => classes!("text-orange-600", "dark:text-orange-400", "font-bold"),
+        Severity::Critical => classes!("text-red-600", "dark:text-red-400", "font-bold"),
+    };
+
+    html! {
+        <div class="flex items-center gap-x-2 text-sm">
+            <span class="w-8 shrink-0 font-mono">{ label }</span>
+            <div class="grow h-3 rounded-2xl bg-brand-bg-message dark:bg-brand-dark-bg-message">
+                <div class={classes!(bar_classes, "h-3", "rounded-2xl")} style={format!("width: {width:.1}%")} />
+            </div>
+            <span class={classes!(text_classes, "w-24", "shrink-0", "text-right", "font-mono")}>
+                { metrics::format_milliseconds(value) }
+            </span>
+        </div>
+    }
+}
@@ -1,12 +1,22 @@
 mod badge;
 mod button;
 mod code_block;
+mod copy_button;
+mod document_tabs;
 mod download_button;
+mod fetch_settings;
 mod file_picker;
+mod found_logs;
 mod icon;
 mod input;
+mod jobs_table;
 mod link;
 mod message;
+mod metrics_chart;
+mod patterns_table;
+mod recent_logs;
+mod select;
+mod sortable_table;
 mod table;
 mod table_item;
 mod table_row;
@@ -16,12 +26,22 @@ mod toolbar;
 pub use badge::Badge;
 pub use button::{Button, ButtonSize};
 pub use code_block::CodeBlock;
+pub use copy_button::CopyButton;
+pub use document_tabs::{DocumentTabs, DocumentTabsEntry};
 pub use download_button::DownloadButton;
-pub use file_picker::FilePicker;
+pub use fetch_settings::FetchSettings;
+pub use file_picker::{FilePicker, FilePickerEntry};
+pub use found_logs::FoundLogs;
 pub use icon::Icon;
 pub use input::Input;
+pub use jobs_table::JobsTable;
 pub use link::Link;
 pub use message::Message;
+pub use metrics_chart::MetricsChart;
+pub use patterns_table::PatternsTable;
+pub use recent_logs::RecentLogs;
+pub use select::Select;
+pub use sortable_table::SortableTable;
 pub use table::Table;
 pub use table_item::TableItem;
 pub use table_row::TableRow;
@@ -0,0 +1,145 @@
+use std::rc::Rc;
+
+use yew::prelude::*;
+
+use crate::{
+    components::{Button, ButtonSize, Message, Table, TableItem, TableRow},
+    patterns::Pattern,
+    timezone::TimestampFormatter,
+};
+
+const ROW_LIMIT_COLLAPSED: usize = 500;
+
+#[derive(Debug, Clone, PartialEq, Properties)]
+pub struct PatternsTableProps {
+    #[prop_or_default]
+    pub classes: Classes,
+
+    pub patterns: Rc<Vec<Pattern>>,
+    pub patterns_by_tag: Rc<Vec<Pattern>>,
+    pub timestamps: TimestampFormatter,
+
+    /// Called with the template of the selected pattern, and its tag if the patterns are grouped
+    /// by tag.
+    pub on_pattern_selected: Callback<(String, Option<String>)>,
+}
+
+#[function_component(PatternsTable)]
+pub fn patterns_table(props: &PatternsTableProps) -> Html {
+    let by_tag = use_state_eq(|| false);
+    let expanded = use_state_eq(|| false);
+
+    let patterns = if *by_tag {
+        &props.patterns_by_tag
+    } else {
+        &props.patterns
+    };
+
+    if patterns.is_empty() {
+        return html! {
+            <Message text="There are no log entries to group." />
+        };
+    }
+
+    let limit = if *expanded {
+        patterns.len()
+    } else {
+        ROW_LIMIT_COLLAPSED
+    };
+
+    let footer = if patterns.len() > limit {
+        html! {
+            <p>
+                { format!("Only the {limit} most frequent of {} patterns are currently shown. ", patterns.len()) }
+
+                <Button
+                    classes={classes!("rounded-2xl")}
+                    size={ButtonSize::Small}
+                    text="Show all"
+                    on_click={Callback::from(move |_| expanded.set(true))}
+                />
+            </p>
+        }
+    } else {
+        html! {}
+    };
+
+    html! {
+        <div class={props.classes.clone()}>
+            <Button
+                classes={classes!("rounded-2xl", "mb-4")}
+                size={ButtonSize::Small}
+                icon={classes!("fas", "fa-tag")}
+                text="Group by tag"
+                active={*by_tag}
+                on_click={Callback::from({
+                    let by_tag = by_tag.clone();
+                    move |_| by_tag.set(!*by_tag)
+                })}
+            />
+
+            <Table>
+                <thead>
+                    <TableRow classes={classes!("text-left")}>
+                        <TableItem tag="th">{ "Count" }</TableItem>
+                        {
+                            if *by_tag {
+                                html! { <TableItem tag="th">{ "Tag" }</TableItem> }
+                            } else {
+                                html! {}
+                            }
+                        }
+                        <TableItem tag="th">{ "Pattern" }</TableItem>
+                        <TableItem tag="th" classes={classes!("min-w-[250px]")}>{ "First" }</TableItem>
+                        <TableItem tag="th" classes={classes!("min-w-[250px]")}>{ "Last" }</TableItem>
+                    </TableRow>
+                </thead>
+                <tbody class="font-mono">
+                    { for patterns.iter().take(limit).map(|pattern| view_pattern_row(props, pattern, *by_tag)) }
+                </tbody>
+            </Table>
+
+            { footer }
+        </div>
+    }
+}
+
+fn view_pattern_row(props: &PatternsTableProps, pattern: &Pattern, by_tag: bool) -> Html {
+    let classes = classes!(
+        pattern.max_level.unwrap_or_default().color(),
+        "cursor-pointer",
+        "hover:bg-brand-primary-hover",
+        "dark:hover:bg-brand-dark-primary-hover",
+        "hover:text-brand-text-primary-hover",
+        "dark:hover:text-brand-dark-text-primary-hover"
+    );
+
+    let template = pattern.template.clone();
+    let tag = if by_tag { pattern.tag.clone() } else { None };
+
+    html! {
+        <TableRow
+            {classes}
+            on_click={props.on_pattern_selected.reform(move |_| (template.clone(), tag.clone()))}
+        >
+            <TableItem>{ pattern.count }</TableItem>
+            {
+                if by_tag {
+                    html! { <TableItem>{ pattern.tag.clone().unwrap_or_default() }</TableItem> }
+                } else {
+                    html! {}
+                }
+            }
+            <TableItem><pre>{ &pattern.template }</pre></TableItem>
+            <TableItem>{ view_timestamp(&props.timestamps, &pattern.first.timestamp) }</TableItem>
+            <TableItem>{ view_timestamp(&props.timestamps, &pattern.last.timestamp) }</TableItem>
+        </TableRow>
+    }
+}
+
+fn view_timestamp(timestamps: &TimestampFormatter, timestamp: &str) -> Html {
+    match timestamps.format(timestamp) {
+        Some(formatted) => html! { <span title={timestamp.to_owned()}>{ formatted }</span> },
+        None => html! { timestamp },
+    }
+}
@@ -0,0 +1,167 @@
+use chrono::Local;
+use yew::prelude::*;
+
+use crate::{
+    cache::{self, RecentLog},
+    components::{Button, ButtonSize, Input, Message, Table, TableItem, TableRow},
+};
+
+#[derive(Debug, Clone, PartialEq, Properties)]
+pub struct RecentLogsProps {
+    #[prop_or_default]
+    pub classes: Classes,
+
+    pub logs: Vec<RecentLog>,
+    /// Called with the URL of the log.
+    pub on_open: Callback<String>,
+    /// Called with the key of the log and its new label.
+    pub on_label_changed: Callback<(String, String)>,
+    /// Called with the key of the log.
+    pub on_remove: Callback<String>,
+    pub on_clear: Callback<()>,
+}
+
+#[function_component(RecentLogs)]
+pub fn recent_logs(props: &RecentLogsProps) -> Html {
+    // Key and pending label of the log whose label is being edited.
+    let editing = use_state_eq(|| None::<(String, String)>);
+
+    if props.logs.is_empty() {
+        return html! {};
+    }
+
+    let total_size = props.logs.iter().map(|log| log.size).sum::<usize>();
+
+    let view_label = |log: &RecentLog| match &*editing {
+        Some((key, label)) if *key == log.key => {
+            let on_change = {
+                let editing = editing.clone();
+                let key = key.clone();
+                Callback::from(move |label| editing.set(Some((key.clone(), label))))
+            };
+
+            let on_submit_maybe = {
+                let editing = editing.clone();
+                let on_label_changed = props.on_label_changed.clone();
+                let key = key.clone();
+                let label = label.clone();
+                Callback::from(move |actually: bool| {
+                    if actually {
+                        on_label_changed.emit((key.clone(), label.trim().to_owned()));
+                        editing.set(None);
+                    }
+                })
+            };
+
+            html! {
+                <Input
+                    classes={classes!("rounded-2xl", "py-1", "text-sm")}
+                    value={label.clone()}
+                    {on_change}
+                    {on_submit_maybe}
+                    placeholder="Label, then Enter ⏎"
+                    autofocus={true}
+                />
+            }
+        }
+        _ => {
+            let on_click = {
+                let editing = editing.clone();
+                let key = log.key.clone();
+                let label = log.label.clone();
+                Callback::from(move |_| editing.set(Some((key.clone(), label.clone()))))
+            };
+
+            html! {
+                <span class="flex items-center gap-x-2">
+                    if log.label.is_empty() {
+                        <span class="italic opacity-50">{ "No label" }</span>
+                    } else {
+                        <span>{ &log.label }</span>
+                    }
+                    <Button size={ButtonSize::Small} icon={classes!("fas", "fa-pen")} {on_click} />
+                </span>
+            }
+        }
+    };
+
+    html! {
+        <Message classes={props.classes.clone()} heading="Recent logs">
+            <Table classes={classes!("text-sm")}>
+                <tbody>
+                    {
+                        for props.logs.iter().map(|log| {
+                            let remote_object = log.remote_object();
+                            let url = log.url.clone();
+                            let key = log.key.clone();
+
+                            html! {
+                                <TableRow>
+                                    <TableItem>
+                                        {
+                                            remote_object
+                                                .as_ref()
+                                                .map(|remote_object| remote_object.platform().to_string())
+                                                .unwrap_or_default()
+                                        }
+                                    </TableItem>
+                                    <TableItem classes={classes!("font-mono")}>
+                                        {
+                                            remote_object
+                                                .as_ref()
+                                                .and_then(|remote_object| remote_object.version().clone())
+                                                .unwrap_or_default()
+                                        }
+                                    </TableItem>
+                                    <TableItem classes={classes!("whitespace-nowrap")}>
+                                        {
+                                            log.fetched_at()
+                                                .map(|time| time.with_timezone(&Local).format("%Y-%m-%d %H:%M").to_string())
+                                                .unwrap_or_default()
+                                        }
+                                    </TableItem>
+                                    <TableItem>{ view_label(log) }</TableItem>
+                                    <TableItem classes={classes!("whitespace-nowrap", "text-right")}>
+                                        { cache::format_size(log.size) }
+                                    </TableItem>
+                                    <TableItem classes={classes!("whitespace-nowrap")}>
+                                        <Button
+                                            size={ButtonSize::Small}
+                                            icon={classes!("fas", "fa-folder-open")}
+                                            text="Open"
+                                            on_click={props.on_open.reform(move |_| url.clone())}
+                                        />
+                                        <Button
+                                            classes={classes!("ml-2")}
+                                            size={ButtonSize::Small}
+                                            icon={classes!("fas", "fa-trash")}
+                                            on_click={props.on_remove.reform(move |_| key.clone())}
+                                        />
+                                    </TableItem>
+                                </TableRow>
+                            }
+                        })
+                    }
+                </tbody>
+            </Table>
+
+            <div class="flex items-center gap-x-4 mt-2 text-sm">
+                <span>
+                    {
+                        format!(
+                            "{} of {} used. Recent logs can be reopened offline.",
+                            cache::format_size(total_size),
+                            cache::format_size(cache::MAX_CACHE_SIZE),
+                        )
+                    }
+                </span>
+                <Button
+                    size={ButtonSize::Small}
+                    icon={classes!("fas", "fa-trash")}
+                    text="Clear cache"
+                    on_click={props.on_clear.reform(|_| ())}
+                />
+            </div>
+        </Message>
+    }
+}
@@ -0,0 +1,56 @@
+use wasm_bindgen::JsCast;
+use web_sys::{Event, HtmlSelectElement};
+use yew::prelude::*;
+
+#[derive(Debug, Clone, PartialEq, Properties)]
+pub struct SelectProps {
+    #[prop_or_default]
+    pub classes: Classes,
+
+    #[prop_or_default]
+    pub title: Option<String>,
+
+    /// Values of the options, along with their text.
+    pub options: Vec<(String, String)>,
+    pub selected: String,
+
+    #[prop_or_else(Callback::noop)]
+    pub on_change: Callback<String>,
+}
+
+#[function_component(Select)]
+pub fn select(props: &SelectProps) -> Html {
+    let classes = classes!(
+        props.classes.clone(),
+        "rounded-2xl",
+        "border-brand-border",
+        "dark:border-brand-dark-border",
+        "shadow-sm",
+        "focus:border-brand-border",
+        "dark:focus:border-brand-dark-border",
+        "focus:ring",
+        "focus:ring-brand-focus",
+        "dark:focus:ring-brand-dark-focus",
+        "focus:ring-opacity-50",
+        "transition",
+        "duration-200",
+        "bg-brand-bg",
+        "dark:bg-brand-dark-bg",
+    );
+
+    html! {
+        <select
+            class={classes}
+            title={props.title.clone()}
+            onchange={props.on_change.reform(|event: Event| {
+                event.target().unwrap().dyn_into::<HtmlSelectElement>().unwrap().value()
+            })}
+        >
+            {
+                for props.options.iter().map(|(value, text)| html! {
+                    <option value={value.clone()} selected={*value == props.selected}>{ text }</option>
+                })
+            }
+        </select>
+    }
+}
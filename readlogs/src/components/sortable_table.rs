@@ -0,0 +1,116 @@
+use std::rc::Rc;
+
+use yew::prelude::*;
+
+use crate::{
+    components::{Button, ButtonSize, DownloadButton, Input, Table, TableItem, TableRow},
+    parsers::GenericTable,
+};
+
+#[derive(Debug, Clone, PartialEq, Properties)]
+pub struct SortableTableProps {
+    #[prop_or_default]
+    pub classes: Classes,
+
+    pub table: Rc<GenericTable>,
+
+    /// Name of the file that the displayed rows are exported to.
+    pub filename: String,
+}
+
+#[function_component(SortableTable)]
+pub fn sortable_table(props: &SortableTableProps) -> Html {
+    // Column that the rows are sorted by and whether in ascending order, if any.
+    let sort = use_state_eq(|| None::<(usize, bool)>);
+    let filters = use_state_eq(|| vec![String::new(); props.table.header.len()]);
+
+    let rows = props.table.displayed_rows(&filters, *sort);
+
+    let view_header = |(column, name): (usize, &String)| {
+        let (icon, active) = match *sort {
+            Some((sorted, true)) if sorted == column => ("fa-sort-up", true),
+            Some((sorted, false)) if sorted == column => ("fa-sort-down", true),
+            _ => ("fa-sort", false),
+        };
+
+        let sort = sort.clone();
+
+        html! {
+            <TableItem tag="th">
+                <Button
+                    classes={classes!("rounded-2xl", "whitespace-nowrap")}
+                    size={ButtonSize::Small}
+                    icon={classes!("fas", icon)}
+                    text={name.clone()}
+                    {active}
+                    on_click={Callback::from(move |_| {
+                        sort.set(match *sort {
+                            Some((sorted, true)) if sorted == column => Some((column, false)),
+                            Some((sorted, false)) if sorted == column => None,
+                            _ => Some((column, true)),
+                        })
+                    })}
+                />
+            </TableItem>
+        }
+    };
+
+    let view_filter = |column: usize| {
+        let filters = filters.clone();
+
+        html! {
+            <TableItem>
+                <Input
+                    classes={classes!("rounded-2xl", "border", "py-1", "text-sm", "w-full")}
+                    value={filters.get(column).cloned().unwrap_or_default()}
+                    on_change={Callback::from(move |value| {
+                        let mut updated = (*filters).clone();
+                        updated.resize(column + 1, String::new());
+                        updated[column] = value;
+                        filters.set(updated);
+                    })}
+                    placeholder="Filter..."
+                />
+            </TableItem>
+        }
+    };
+
+    html! {
+        <div class={props.classes.clone()}>
+            <Table>
+                <thead>
+                    <TableRow classes={classes!("text-left")}>
+                        { for props.table.header.iter().enumerate().map(view_header) }
+                    </TableRow>
+                    <TableRow classes={classes!("not-prose")}>
+                        { for (0..props.table.header.len()).map(view_filter) }
+                    </TableRow>
+                </thead>
+                <tbody class="font-mono">
+                    {
+                        for rows.iter().map(|row| html! {
+                            <TableRow>
+                                { for row.iter().map(|item| html! { <TableItem>{ item }</TableItem> }) }
+                            </TableRow>
+                        })
+                    }
+                </tbody>
+            </Table>
+
+            <div class="flex items-center gap-x-2 mt-2 mb-4 text-sm">
+                <span>{ format!("{} of {} rows", rows.len(), props.table.rows.len()) }</span>
+                <DownloadButton
+                    classes={classes!("rounded-2xl")}
+                    size={ButtonSize::Small}
+                    icon={classes!("fas", "fa-file-csv")}
+                    text="Download CSV"
+                    content={
+                        let (table, filters, sort) = (Rc::clone(&props.table), (*filters).clone(), *sort);
+                        Callback::from(move |_| table.to_csv(&table.displayed_rows(&filters, sort)))
+                    }
+                    filename={props.filename.clone()}
+                />
+            </div>
+        </div>
+    }
+}
@@ -9,6 +9,9 @@ pub struct TableItemProps {
 
     #[prop_or_else(|| "td".to_owned())]
     pub tag: String,
+
+    #[prop_or_default]
+    pub colspan: Option<usize>,
 }
 
 #[function_component(TableItem)]
@@ -16,7 +19,7 @@ pub fn table_item(props: &TableItemProps) -> Html {
     let classes = classes!(props.classes.clone(), "first:pl-2", "last:pr-2");
 
     html! {
-        <@{props.tag.clone()} class={classes}>
+        <@{props.tag.clone()} class={classes} colspan={props.colspan.map(|colspan| colspan.to_string())}>
             { props.children.clone() }
         </@>
     }
@@ -7,6 +7,8 @@ pub struct TableRowProps {
     #[prop_or_default]
     pub children: Children,
 
+    #[prop_or_default]
+    pub id: Option<String>,
     #[prop_or_default]
     pub on_click: Callback<MouseEvent>,
 }
@@ -14,7 +16,7 @@ pub struct TableRowProps {
 #[function_component(TableRow)]
 pub fn table_row(props: &TableRowProps) -> Html {
     html! {
-        <tr class={props.classes.clone()} onclick={props.on_click.clone()}>
+        <tr id={props.id.clone()} class={props.classes.clone()} onclick={props.on_click.clone()}>
             { props.children.clone() }
         </tr>
     }
@@ -0,0 +1,259 @@
+use chrono::{prelude::*, Duration};
+use derive_more::Display;
+use strum_macros::{EnumIter, EnumString};
+use yew::{html::Scope, prelude::*};
+
+use crate::{
+    components::{Icon, TableItem},
+    parsers::LogEntry,
+    timeline,
+    timezone::TimestampFormatter,
+    Model, Msg,
+};
+
+/// Time between log entries from which a delta is highlighted.
+const LARGE_DELTA_MILLISECONDS: i64 = 1000;
+
+/// Columns with the time between log entries that are shown in log tables.
+#[derive(Debug, Display, Clone, Copy, Default, PartialEq, Eq, EnumIter, EnumString)]
+pub enum DeltaColumns {
+    #[default]
+    #[display(fmt = "No deltas")]
+    #[strum(serialize = "No deltas")]
+    None,
+    #[display(fmt = "Since previous")]
+    #[strum(serialize = "Since previous")]
+    Previous,
+    #[display(fmt = "Since anchor")]
+    #[strum(serialize = "Since anchor")]
+    Anchor,
+    #[display(fmt = "Since previous and anchor")]
+    #[strum(serialize = "Since previous and anchor")]
+    Both,
+}
+
+impl DeltaColumns {
+    pub fn previous(&self) -> bool {
+        matches!(self, DeltaColumns::Previous | DeltaColumns::Both)
+    }
+
+    pub fn anchor(&self) -> bool {
+        matches!(self, DeltaColumns::Anchor | DeltaColumns::Both)
+    }
+
+    pub fn count(&self) -> usize {
+        usize::from(self.previous()) + usize::from(self.anchor())
+    }
+}
+
+/// Log entry that the time in the "since anchor" column is relative to.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Anchor {
+    /// Path of the section that the entry is in (see [`section_path`](crate::parsers::section_path)).
+    pub section: String,
+    /// Number of the entry in its section.
+    pub number: usize,
+    pub time: DateTime<FixedOffset>,
+}
+
+impl Anchor {
+    pub fn new(
+        section: &str,
+        number: usize,
+        entry: &LogEntry,
+        timestamps: &TimestampFormatter,
+    ) -> Option<Self> {
+        Some(Self {
+            section: section.to_owned(),
+            number,
+            time: timestamps.date_time(&entry.timestamp)?,
+        })
+    }
+
+    pub fn is(&self, section: &str, number: usize) -> bool {
+        self.section == section && self.number == number
+    }
+}
+
+/// Time since the previous entry, for each of the entries (which don't have to be consecutive).
+pub fn since_previous<'a>(
+    entries: impl IntoIterator<Item = &'a LogEntry>,
+    timestamps: &TimestampFormatter,
+) -> Vec<Option<Duration>> {
+    let mut previous_time = None;
+
+    entries
+        .into_iter()
+        .map(|entry| {
+            let time = timestamps.date_time(&entry.timestamp);
+            let delta = previous_time
+                .zip(time)
+                .map(|(previous, time)| time - previous);
+
+            if time.is_some() {
+                previous_time = time;
+            }
+
+            delta
+        })
+        .collect()
+}
+
+pub fn is_large(delta: Duration) -> bool {
+    delta.num_milliseconds().abs() >= LARGE_DELTA_MILLISECONDS
+}
+
+/// Formats the delta with an explicit sign, e.g. `+1.500 s`.
+pub fn format_delta(delta: Duration) -> String {
+    let text = timeline::format_duration(delta);
+
+    if delta < Duration::zero() {
+        text
+    } else {
+        format!("+{text}")
+    }
+}
+
+fn delta_classes(delta: Duration) -> Classes {
+    if is_large(delta) {
+        classes!("text-orange-600", "dark:text-orange-400", "font-bold")
+    } else {
+        classes!()
+    }
+}
+
+pub fn view_delta(delta: Option<Duration>) -> Html {
+    match delta {
+        Some(delta) => html! {
+            <TableItem classes={classes!(delta_classes(delta), "whitespace-nowrap", "text-right")}>
+                { format_delta(delta) }
+            </TableItem>
+        },
+        None => html! { <TableItem /> },
+    }
+}
+
+/// Renders the time since the anchor, which can be clicked to make the entry the anchor
+/// (or to clear the anchor, if the entry already is one).
+pub fn view_since_anchor(
+    section: &str,
+    number: usize,
+    entry: &LogEntry,
+    anchor: Option<&Anchor>,
+    timestamps: &TimestampFormatter,
+    link: &Scope<Model>,
+) -> Html {
+    let is_anchor = matches!(anchor, Some(anchor) if anchor.is(section, number));
+
+    let (content, classes, title) = if is_anchor {
+        (
+            html! { <Icon icon={classes!("fas", "fa-anchor")} /> },
+            classes!(),
+            "Clear anchor",
+        )
+    } else {
+        match anchor.zip(timestamps.date_time(&entry.timestamp)) {
+            Some((anchor, time)) => {
+                let delta = time - anchor.time;
+                (
+                    html! { format_delta(delta) },
+                    delta_classes(delta),
+                    "Set as anchor",
+                )
+            }
+            None => (
+                html! { <Icon icon={classes!("fas", "fa-anchor")} /> },
+                classes!("opacity-25", "hover:opacity-100"),
+                "Set as anchor",
+            ),
+        }
+    };
+
+    let new_anchor = if is_anchor {
+        None
+    } else {
+        Anchor::new(section, number, entry, timestamps)
+    };
+
+    html! {
+        <TableItem classes={classes!("whitespace-nowrap", "text-right")}>
+            <button
+                {title}
+                class={classes}
+                onclick={link.callback(move |event: MouseEvent| {
+                    // Clicking the row itself opens the entry details instead.
+                    event.stop_propagation();
+                    Msg::UpdateAnchor(new_anchor.clone())
+                })}
+            >
+                { content }
+            </button>
+        </TableItem>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+
+    use super::*;
+    use crate::{parsers::test_entry, DisplayTimezone};
+
+    #[test]
+    fn since_previous_ok() {
+        let entries = [
+            test_entry("2023-01-22 12:00:00.000 UTC", "1", "main", "Tag", "Message"),
+            test_entry("2023-01-22 12:00:00.250 UTC", "1", "main", "Tag", "Message"),
+            test_entry("invalid", "1", "main", "Tag", "Message"),
+            test_entry("2023-01-22 12:00:02.250 UTC", "1", "main", "Tag", "Message"),
+        ];
+
+        assert_eq!(
+            since_previous(
+                &entries,
+                &TimestampFormatter::new(DisplayTimezone::Original, None)
+            ),
+            vec![
+                None,
+                Some(Duration::milliseconds(250)),
+                None,
+                Some(Duration::seconds(2)),
+            ]
+        );
+    }
+
+    #[test]
+    fn since_previous_in_submitter_timezone() {
+        let entries = [
+            test_entry(
+                "2023-01-22 12:00:00.000 +01:00",
+                "1",
+                "main",
+                "Tag",
+                "Message",
+            ),
+            test_entry("2023-01-22 12:00:01.000", "1", "main", "Tag", "Message"),
+        ];
+        let timestamps =
+            TimestampFormatter::new(DisplayTimezone::Original, FixedOffset::east_opt(3600));
+
+        assert_eq!(
+            since_previous(&entries, &timestamps),
+            vec![None, Some(Duration::seconds(1))]
+        );
+    }
+
+    #[test_case(Duration::milliseconds(250) => "+250 ms"; "positive")]
+    #[test_case(Duration::zero() => "+0 ms"; "zero")]
+    #[test_case(Duration::milliseconds(-1500) => "-1.500 s"; "negative")]
+    fn format_delta_ok(delta: Duration) -> String {
+        format_delta(delta)
+    }
+
+    #[test_case(Duration::milliseconds(999) => false; "small")]
+    #[test_case(Duration::milliseconds(1000) => true; "large")]
+    #[test_case(Duration::milliseconds(-5000) => true; "large negative")]
+    fn is_large_ok(delta: Duration) -> bool {
+        is_large(delta)
+    }
+}
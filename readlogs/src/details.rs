@@ -0,0 +1,390 @@
+use std::{ops::RangeInclusive, rc::Rc};
+
+use yew::{html::Scope, prelude::*};
+
+use crate::{
+    components::{Button, ButtonSize, CopyButton, Table, TableItem, TableRow},
+    parsers::{section_path, LogEntry, Section},
+    rich_text,
+    timezone::TimestampFormatter,
+    Highlighter, Model, Msg,
+};
+
+/// Number of entries shown before and after the selected one in the detail drawer.
+const NEIGHBOURS: usize = 2;
+
+/// Log entries selected by clicking them, which are shown in the detail drawer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EntrySelection {
+    /// Path of the section that the entries are in (see [`section_path`]).
+    pub section: String,
+    /// Number of the last clicked entry in its section.
+    pub number: usize,
+    /// Numbers of the selected entries, which include `number`.
+    pub range: RangeInclusive<usize>,
+}
+
+impl EntrySelection {
+    /// Selects the entry, or extends the current selection to it if `extend` is `true` and it's
+    /// in the same section.
+    pub fn select(current: Option<&Self>, section: &str, number: usize, extend: bool) -> Self {
+        let range = match current {
+            Some(current) if extend && current.section == section => {
+                let start = *current.range.start().min(&number);
+                let end = *current.range.end().max(&number);
+                start..=end
+            }
+            _ => number..=number,
+        };
+
+        Self {
+            section: section.to_owned(),
+            number,
+            range,
+        }
+    }
+
+    pub fn contains(&self, section: &str, number: usize) -> bool {
+        self.section == section && self.range.contains(&number)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportFormat {
+    Text,
+    /// Text in a code block, for bug trackers.
+    Markdown,
+}
+
+/// The entry on one line: its timestamp, level, metadata fields and message.
+pub fn format_entry(entry: &LogEntry) -> String {
+    let level = entry
+        .level
+        .map(|level| level.to_string())
+        .unwrap_or_else(|| "-".to_owned());
+
+    [entry.timestamp.as_str(), level.as_str()]
+        .into_iter()
+        .chain(
+            entry
+                .meta
+                .fields()
+                .into_iter()
+                .filter(|field| !field.is_empty()),
+        )
+        .chain([entry.message.as_str()])
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// The entries with a heading saying where they're from, ready to be pasted somewhere.
+pub fn format_report(
+    source: &str,
+    section: &str,
+    entries: &[(usize, &LogEntry)],
+    format: ReportFormat,
+) -> String {
+    let numbers = match (entries.first(), entries.last()) {
+        (Some((first, _)), Some((last, _))) if first != last => {
+            format!("entries {first}–{last}")
+        }
+        (Some((first, _)), _) => format!("entry {first}"),
+        _ => "no entries".to_owned(),
+    };
+
+    let lines = entries
+        .iter()
+        .map(|(_, entry)| format_entry(entry))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    match format {
+        ReportFormat::Text => format!("{source}, {section}, {numbers}\n{lines}\n"),
+        ReportFormat::Markdown => {
+            // The fence has to be longer than any run of backticks in the messages.
+            let longest_backticks = lines
+                .split(|c| c != '`')
+                .map(str::len)
+                .max()
+                .unwrap_or_default();
+            let fence = "`".repeat(longest_backticks.max(2) + 1);
+
+            format!("**{source}**, {section}, {numbers}\n\n{fence}text\n{lines}\n{fence}\n")
+        }
+    }
+}
+
+/// Section with the path among the sections and their subsections, if there is one.
+pub fn find_section<'a>(
+    sections: &'a [Section<LogEntry>],
+    path: &str,
+) -> Option<&'a Section<LogEntry>> {
+    find_subsection(sections, None, path)
+}
+
+fn find_subsection<'a>(
+    sections: &'a [Section<LogEntry>],
+    parent: Option<&str>,
+    path: &str,
+) -> Option<&'a Section<LogEntry>> {
+    sections.iter().find_map(|section| {
+        let section_path = section_path(parent, &section.name);
+
+        if section_path == path {
+            Some(section)
+        } else {
+            find_subsection(&section.subsections, Some(&section_path), path)
+        }
+    })
+}
+
+/// Drawer with everything about the selected entry, its neighbours, and ways to copy the selection.
+pub fn view(
+    section: &Section<LogEntry>,
+    selection: &EntrySelection,
+    source: &str,
+    timestamps: &TimestampFormatter,
+    link: &Scope<Model>,
+) -> Html {
+    let Some(entry) = selection
+        .number
+        .checked_sub(1)
+        .and_then(|index| section.content.get(index))
+    else {
+        return html! {};
+    };
+
+    let selected = section
+        .content
+        .iter()
+        .enumerate()
+        .map(|(index, entry)| (index + 1, entry))
+        .filter(|(number, _)| selection.range.contains(number))
+        .collect::<Vec<_>>();
+
+    let report = |format| Rc::new(format_report(source, &section.name, &selected, format));
+
+    let highlighter = Highlighter::default();
+
+    let fields = entry
+        .meta
+        .column_names()
+        .into_iter()
+        .zip(entry.meta.fields())
+        .map(|(name, value)| {
+            html! {
+                <TableRow>
+                    <TableItem tag="th">{ name }</TableItem>
+                    <TableItem classes={classes!("font-mono", "break-all")}>{ value }</TableItem>
+                </TableRow>
+            }
+        });
+
+    let first_neighbour = selection.number.saturating_sub(NEIGHBOURS).max(1);
+    let neighbours = section
+        .content
+        .iter()
+        .enumerate()
+        .map(|(index, entry)| (index + 1, entry))
+        .skip(first_neighbour - 1)
+        .take(2 * NEIGHBOURS + 1)
+        .map(|(number, neighbour)| {
+            let mut classes = neighbour.level.unwrap_or_default().color();
+            classes.push("cursor-pointer");
+            if selection.range.contains(&number) {
+                classes.push(classes!(
+                    "bg-brand-bg-message",
+                    "dark:bg-brand-dark-bg-message"
+                ));
+            }
+
+            let section = selection.section.clone();
+
+            html! {
+                <TableRow
+                    {classes}
+                    on_click={link.callback(move |event: MouseEvent| {
+                        Msg::SelectEntry(section.clone(), number, event.shift_key())
+                    })}
+                >
+                    <TableItem>{ number }</TableItem>
+                    <TableItem classes={classes!("whitespace-nowrap")}>
+                        { timestamps.view(&neighbour.timestamp, &highlighter, None) }
+                    </TableItem>
+                    <TableItem classes={classes!("truncate", "max-w-xs")}>{ &neighbour.message }</TableItem>
+                </TableRow>
+            }
+        });
+
+    let selected_text = match selected.len() {
+        1 => "1 entry selected (shift-click another one to select a range)".to_owned(),
+        count => format!("{count} entries selected"),
+    };
+
+    html! {
+        <aside class="fixed top-0 right-0 bottom-0 z-10 w-full max-w-xl overflow-y-auto p-4 shadow-lg bg-brand-bg dark:bg-brand-dark-bg">
+            <div class="flex items-center mb-4">
+                <h2 class="grow font-bold">
+                    { format!("Entry {} in {}", selection.number, section.name) }
+                </h2>
+                <Button
+                    size={ButtonSize::Small}
+                    icon={classes!("fas", "fa-times")}
+                    on_click={link.callback(|_| Msg::CloseEntryDetails)}
+                />
+            </div>
+
+            <Table classes={classes!("text-sm", "mb-4")}>
+                <tbody>
+                    <TableRow>
+                        <TableItem tag="th">{ "Timestamp" }</TableItem>
+                        <TableItem classes={classes!("font-mono")}>
+                            { timestamps.view(&entry.timestamp, &highlighter, None) }
+                        </TableItem>
+                    </TableRow>
+                    <TableRow>
+                        <TableItem tag="th">{ "Level" }</TableItem>
+                        <TableItem classes={entry.level.unwrap_or_default().color()}>
+                            { entry.level.map(|level| level.to_string()).unwrap_or_else(|| "None".to_owned()) }
+                        </TableItem>
+                    </TableRow>
+                    { for fields }
+                    <TableRow>
+                        <TableItem tag="th">{ "Source" }</TableItem>
+                        <TableItem classes={classes!("break-all")}>{ source }</TableItem>
+                    </TableRow>
+                </tbody>
+            </Table>
+
+            <pre
+                onclick={link.batch_callback(|event: MouseEvent| rich_text::pivot_target(&event).map(Msg::Pivot))}
+                class="mb-4 p-2 rounded-2xl text-sm whitespace-pre-wrap break-words select-text bg-brand-bg-message dark:bg-brand-dark-bg-message">
+                { rich_text::view(&entry.message, &highlighter, None) }
+            </pre>
+
+            <p class="font-bold">{ "Neighbours" }</p>
+            <Table classes={classes!("text-sm", "font-mono", "mb-4")}>
+                <tbody>
+                    { for neighbours }
+                </tbody>
+            </Table>
+
+            <p class="text-sm mb-2">{ selected_text }</p>
+            <div class="flex gap-x-2">
+                <CopyButton
+                    icon={classes!("fas", "fa-copy")}
+                    text="Copy as text"
+                    content={report(ReportFormat::Text)}
+                />
+                <CopyButton
+                    icon={classes!("fab", "fa-markdown")}
+                    text="Copy as Markdown"
+                    content={report(ReportFormat::Markdown)}
+                />
+            </div>
+        </aside>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+
+    use super::*;
+    use crate::{parsers, LogLevel};
+
+    fn test_entry(message: &str) -> LogEntry {
+        LogEntry {
+            level: Some(LogLevel::Warn),
+            ..parsers::test_entry(
+                "2022-01-02 03:04:05.678 +00:00",
+                "5.30.0",
+                "main",
+                "Tag",
+                message,
+            )
+        }
+    }
+
+    #[test_case(None, "Logs", 5, true => (5, 5); "nothing selected")]
+    #[test_case(Some(3..=4), "Logs", 7, true => (3, 7); "extends forward")]
+    #[test_case(Some(3..=4), "Logs", 1, true => (1, 4); "extends backward")]
+    #[test_case(Some(3..=4), "Logs", 7, false => (7, 7); "without extending")]
+    #[test_case(Some(3..=4), "Other", 7, true => (7, 7); "other section")]
+    fn select(
+        current: Option<RangeInclusive<usize>>,
+        section: &str,
+        number: usize,
+        extend: bool,
+    ) -> (usize, usize) {
+        let current = current.map(|range| EntrySelection {
+            section: "Logs".to_owned(),
+            number: *range.start(),
+            range,
+        });
+
+        let selection = EntrySelection::select(current.as_ref(), section, number, extend);
+        (*selection.range.start(), *selection.range.end())
+    }
+
+    #[test]
+    fn format_entry_ok() {
+        assert_eq!(
+            format_entry(&test_entry("Something happened")),
+            "2022-01-02 03:04:05.678 +00:00 Warn 5.30.0 main Tag Something happened"
+        );
+    }
+
+    #[test]
+    fn format_report_text() {
+        let first = test_entry("First");
+        let second = test_entry("Second");
+
+        assert_eq!(
+            format_report(
+                "Source",
+                "Logs",
+                &[(4, &first), (5, &second)],
+                ReportFormat::Text
+            ),
+            "Source, Logs, entries 4–5\n\
+             2022-01-02 03:04:05.678 +00:00 Warn 5.30.0 main Tag First\n\
+             2022-01-02 03:04:05.678 +00:00 Warn 5.30.0 main Tag Second\n"
+        );
+    }
+
+    #[test_case("No backticks" => "```"; "plain")]
+    #[test_case("Some ```code``` here" => "````"; "longer fence")]
+    fn format_report_markdown_fence(message: &str) -> String {
+        let entry = test_entry(message);
+        let report = format_report("Source", "Logs", &[(1, &entry)], ReportFormat::Markdown);
+
+        assert!(report.starts_with("**Source**, Logs, entry 1\n\n"));
+        report.lines().last().unwrap().to_owned()
+    }
+
+    #[test_case("Logs/Lifecycle" => Some("First".to_owned()); "subsection")]
+    #[test_case("Other/Lifecycle" => Some("Second".to_owned()); "subsection with the same name")]
+    #[test_case("Lifecycle" => None; "name without parent")]
+    fn find_section_by_path(path: &str) -> Option<String> {
+        let section = |name: &str, content, subsections| Section {
+            name: name.to_owned(),
+            content,
+            subsections,
+        };
+        let sections = vec![
+            section(
+                "Logs",
+                vec![],
+                vec![section("Lifecycle", vec![test_entry("First")], vec![])],
+            ),
+            section(
+                "Other",
+                vec![],
+                vec![section("Lifecycle", vec![test_entry("Second")], vec![])],
+            ),
+        ];
+
+        find_section(&sections, path).map(|section| section.content[0].message.clone())
+    }
+}
@@ -0,0 +1,125 @@
+use anyhow::{anyhow, Context};
+use js_sys::{Promise, Reflect, Uint8Array};
+use reqwasm::http::Response;
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{window, ReadableStreamDefaultReader};
+
+use crate::cache;
+
+/// How much of a response body has been received.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Progress {
+    /// In bytes.
+    pub received: usize,
+    /// From the `Content-Length` header, in bytes, if it's known.
+    ///
+    /// For compressed responses, this is the compressed size, while the received bytes are
+    /// already decompressed by the browser.
+    pub total: Option<usize>,
+}
+
+impl Progress {
+    /// Received part of the total, between 0 and 1, if it can be determined.
+    pub fn fraction(&self) -> Option<f64> {
+        match self.total {
+            Some(total) if total > 0 && self.received <= total => {
+                Some(self.received as f64 / total as f64)
+            }
+            _ => None,
+        }
+    }
+
+    pub fn text(&self) -> String {
+        match (self.fraction(), self.total) {
+            (Some(_), Some(total)) => format!(
+                "{} of {}",
+                cache::format_size(self.received),
+                cache::format_size(total)
+            ),
+            _ => format!("{} received", cache::format_size(self.received)),
+        }
+    }
+}
+
+fn js_error(error: JsValue) -> anyhow::Error {
+    anyhow!("{error:?}")
+}
+
+/// Reads the whole response body chunk by chunk, reporting the progress after each one.
+pub async fn read_body(
+    response: &Response,
+    on_progress: impl Fn(Progress),
+) -> anyhow::Result<Vec<u8>> {
+    let mut progress = Progress {
+        received: 0,
+        total: response
+            .headers()
+            .get("content-length")
+            .and_then(|length| length.parse().ok()),
+    };
+
+    let Some(body) = response.body() else {
+        return Ok(vec![]);
+    };
+
+    let reader = body
+        .get_reader()
+        .unchecked_into::<ReadableStreamDefaultReader>();
+    let mut bytes = vec![];
+
+    loop {
+        let chunk = JsFuture::from(reader.read())
+            .await
+            .map_err(js_error)
+            .context("couldn't read the response")?;
+
+        let done = Reflect::get(&chunk, &JsValue::from_str("done"))
+            .map_err(js_error)?
+            .is_truthy();
+        if done {
+            break;
+        }
+
+        let value = Reflect::get(&chunk, &JsValue::from_str("value")).map_err(js_error)?;
+        bytes.extend(Uint8Array::new(&value).to_vec());
+
+        progress.received = bytes.len();
+        on_progress(progress);
+    }
+
+    Ok(bytes)
+}
+
+/// Resolves after the browser has had a chance to render, so that the progress of long
+/// synchronous work can be shown in between its steps.
+pub async fn yield_to_browser() {
+    let promise = Promise::new(&mut |resolve, _| {
+        if let Some(window) = window() {
+            let _ = window.set_timeout_with_callback(&resolve);
+        }
+    });
+
+    let _ = JsFuture::from(promise).await;
+}
+
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+
+    use super::*;
+
+    #[test_case(Progress { received: 512, total: Some(2048) } => Some(0.25); "known total")]
+    #[test_case(Progress { received: 512, total: None } => None; "unknown total")]
+    #[test_case(Progress { received: 4096, total: Some(2048) } => None; "decompressed")]
+    #[test_case(Progress { received: 0, total: Some(0) } => None; "empty")]
+    fn progress_fraction(progress: Progress) -> Option<f64> {
+        progress.fraction()
+    }
+
+    #[test_case(Progress { received: 512, total: Some(2048) } => "512 B of 2.0 KB"; "known total")]
+    #[test_case(Progress { received: 4096, total: Some(2048) } => "4.0 KB received"; "decompressed")]
+    fn progress_text(progress: Progress) -> String {
+        progress.text()
+    }
+}
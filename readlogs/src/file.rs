@@ -1,68 +1,277 @@
 use std::rc::Rc;
 
-use anyhow::Context;
-use yew::prelude::*;
+use anyhow::anyhow;
+use chrono::FixedOffset;
+use yew::{html::Scope, prelude::*};
 
 use crate::{
-    components::{ButtonSize, CodeBlock, DownloadButton, Message, Title, TitleLevel},
+    components::{
+        ButtonSize, CodeBlock, DownloadButton, JobsTable, Message, PatternsTable, Title, TitleLevel,
+    },
+    details::{self, EntrySelection},
+    jobs::{self, Job},
     parsers::*,
-    Platform, RemoteObject, RenderedLogSection, SearchQuery, Tab,
+    patterns::{self, Pattern},
+    summary::Summary,
+    timezone::{self, TimestampFormatter},
+    DisplayOptions, DisplayTimezone, Document, Highlighter, LogLevel, Model, Msg, Platform,
+    RemoteObject, RenderedLogSection, SearchQuery, SearchSummary, Tab,
 };
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileStatus {
+    Ok,
+    /// Parsed, but there were problems that may have caused some of the content to be lost.
+    Partial,
+    Failed,
+}
+
 #[derive(Debug)]
 pub struct File {
     remote_object: RemoteObject,
-    name: Option<Rc<LogFilename>>,
+    name: Option<ArchiveFilename>,
     text: Rc<String>,
-    parsed: anyhow::Result<Content>,
+    warnings: Vec<String>,
+    parsed: anyhow::Result<(&'static dyn LogFormat, Rc<Content>)>,
+    patterns: Rc<Vec<Pattern>>,
+    patterns_by_tag: Rc<Vec<Pattern>>,
+    summary: Rc<Summary>,
+    jobs: Rc<Vec<Job>>,
+    /// Offset of the submitter's device, if known.
+    submitter_offset: Option<FixedOffset>,
 }
 
 impl File {
+    /// Like `from_text`, but replaces invalid UTF-8 sequences (with a warning) instead of failing.
+    pub fn from_bytes(
+        remote_object: RemoteObject,
+        name: Option<ArchiveFilename>,
+        bytes: Vec<u8>,
+    ) -> Self {
+        match String::from_utf8(bytes) {
+            Ok(text) => Self::from_text(remote_object, name, text),
+            Err(error) => {
+                let text = String::from_utf8_lossy(error.as_bytes()).into_owned();
+                let mut file = Self::from_text(remote_object, name, text);
+
+                file.warnings.push(format!(
+                    "The file is not valid UTF-8 ({}), so invalid sequences were replaced with `{}`.",
+                    error.utf8_error(),
+                    char::REPLACEMENT_CHARACTER
+                ));
+
+                file
+            }
+        }
+    }
+
     pub fn from_text(
         remote_object: RemoteObject,
-        name: Option<Rc<LogFilename>>,
+        name: Option<ArchiveFilename>,
         text: String,
     ) -> Self {
-        let parsed = Content::parse(remote_object.platform(), &text).context(format!(
-            "failed to parse {} debug log file",
-            remote_object.platform()
-        ));
+        let parsed = match &name {
+            Some(ArchiveFilename::Other(name)) => {
+                Err(anyhow!("`{name}` doesn't seem to be a debug log file"))
+            }
+            _ => format::parse(remote_object.platform(), &text)
+                .map(|(format, parsed)| (format, Rc::new(parsed))),
+        };
+
+        let log_filename = match &name {
+            Some(ArchiveFilename::Log(name)) => Some(name.as_ref()),
+            _ => None,
+        };
+
+        let patterns_by_tag = match &parsed {
+            Ok((_, parsed)) => patterns::cluster_log_entries(&parsed.logs),
+            Err(_) => vec![],
+        };
+
+        let jobs = match &parsed {
+            Ok((_, parsed)) => jobs::jobs(&parsed.information, &parsed.logs),
+            Err(_) => vec![],
+        };
+
+        let submitter_offset = match &parsed {
+            Ok((_, parsed)) => timezone::submitter_offset(&parsed.logs),
+            Err(_) => None,
+        };
+
+        let summary = Summary::new(
+            &remote_object,
+            log_filename,
+            parsed.as_ref().ok().map(|(_, parsed)| parsed.as_ref()),
+            &TimestampFormatter::new(DisplayTimezone::Original, submitter_offset),
+        );
 
         Self {
             remote_object,
             name,
             text: Rc::new(text),
+            warnings: vec![],
             parsed,
+            patterns: Rc::new(patterns::ignore_tags(&patterns_by_tag)),
+            patterns_by_tag: Rc::new(patterns_by_tag),
+            summary: Rc::new(summary),
+            jobs: Rc::new(jobs),
+            submitter_offset,
         }
     }
 
-    pub fn view(&self, tab: Tab, query: &SearchQuery) -> Html {
+    pub fn status(&self) -> FileStatus {
+        match (&self.parsed, self.warnings.is_empty()) {
+            (Err(_), _) => FileStatus::Failed,
+            (Ok(_), false) => FileStatus::Partial,
+            (Ok(_), true) => FileStatus::Ok,
+        }
+    }
+
+    /// Format that the file was parsed in, if it was.
+    pub fn format(&self) -> Option<&'static dyn LogFormat> {
+        self.parsed.as_ref().ok().map(|(format, _)| *format)
+    }
+
+    fn timestamps(&self, timezone: DisplayTimezone) -> TimestampFormatter {
+        TimestampFormatter::new(timezone, self.submitter_offset)
+    }
+
+    pub fn search_summary(
+        &self,
+        query: &SearchQuery,
+        highlighter: &Highlighter,
+        timezone: DisplayTimezone,
+    ) -> SearchSummary {
+        match &self.parsed {
+            Ok((_, parsed)) => {
+                parsed.search_summary(query, highlighter, &self.timestamps(timezone))
+            }
+            Err(_) => SearchSummary::default(),
+        }
+    }
+
+    pub fn displayed_levels(
+        &self,
+        query: &SearchQuery,
+        highlighter: &Highlighter,
+        timezone: DisplayTimezone,
+    ) -> Vec<LogLevel> {
+        match &self.parsed {
+            Ok((_, parsed)) => {
+                parsed.displayed_levels(query, highlighter, &self.timestamps(timezone))
+            }
+            Err(_) => vec![],
+        }
+    }
+
+    pub fn count_matches(
+        &self,
+        query: &SearchQuery,
+        highlighter: &Highlighter,
+        timezone: DisplayTimezone,
+    ) -> usize {
+        match &self.parsed {
+            Ok((_, parsed)) => parsed.count_matches(query, highlighter, &self.timestamps(timezone)),
+            Err(_) => 0,
+        }
+    }
+
+    pub fn view(
+        &self,
+        document: &Document,
+        highlighter: &Highlighter,
+        options: &DisplayOptions,
+        link: &Scope<Model>,
+    ) -> Html {
+        let tab = document.tab;
+        let timestamps = self.timestamps(document.timezone);
+
         let title = match tab {
             Tab::Information => html! {
                 <Title level={TitleLevel::H1} text={format!("{} ({})", tab, self.remote_object.platform())}/>
             },
-            Tab::Logs => html! {},
-            Tab::Raw => html! {
+            Tab::Logs | Tab::Threads => html! {},
+            Tab::Jobs | Tab::Patterns | Tab::Raw => html! {
                 <Title level={TitleLevel::H1} text={tab.to_string()}/>
             },
         };
 
         let content = match tab {
-            Tab::Information => match &self.parsed {
-                Ok(parsed) => parsed.view_information(self.remote_object.platform()),
-                Err(error) => self.view_parsing_error(error),
+            Tab::Information => html! {
+                <>
+                    { self.summary.view(&timestamps) }
+
+                    <DownloadButton
+                        classes={classes!("rounded-2xl", "mb-8")}
+                        size={ButtonSize::Medium}
+                        icon={classes!("fas", "fa-download")}
+                        text="Download summary (JSON)"
+                        content={
+                            let summary = Rc::clone(&self.summary);
+                            Callback::from(move |_| summary.to_json())
+                        }
+                        filename={format!("{}-summary.json", self.export_filename_stem())}
+                    />
+
+                    {
+                        match &self.parsed {
+                            Ok((format, parsed)) => parsed.view_information(*format, &document.information, link),
+                            Err(error) => self.view_parsing_error(error),
+                        }
+                    }
+                </>
             },
             Tab::Logs => match &self.parsed {
-                Ok(parsed) => RenderedLogSection {
+                Ok((format, parsed)) => RenderedLogSection {
                     title: tab.to_string(),
-                    subsections: parsed.view_logs(query),
+                    subsections: parsed.view_logs(&LogViewContext {
+                        format: *format,
+                        query: &document.active_query,
+                        highlighter,
+                        timestamps: &timestamps,
+                        options,
+                        anchor: document.anchor.as_ref(),
+                        selection: document.selection.as_ref(),
+                        link,
+                    }),
                     ..Default::default()
                 }
-                .view(
-                    self.remote_object.platform().is_android(),
-                    self.remote_object.platform().is_android(),
-                    true,
-                ),
+                .view(parsed.logs.len() > 1, parsed.logs.len() > 1, true),
+                Err(error) => self.view_parsing_error(error),
+            },
+            Tab::Threads => match &self.parsed {
+                Ok((_, parsed)) => RenderedLogSection {
+                    title: tab.to_string(),
+                    subsections: parsed.view_swimlanes(
+                        &document.active_query,
+                        highlighter,
+                        &timestamps,
+                        &document.swimlanes,
+                        link,
+                    ),
+                    ..Default::default()
+                }
+                .view(true, true, true),
+                Err(error) => self.view_parsing_error(error),
+            },
+            Tab::Jobs => match &self.parsed {
+                Ok(_) => html! {
+                    <JobsTable
+                        jobs={Rc::clone(&self.jobs)}
+                        on_job_selected={link.callback(Msg::FilterByRegex)}
+                    />
+                },
+                Err(error) => self.view_parsing_error(error),
+            },
+            Tab::Patterns => match &self.parsed {
+                Ok(_) => html! {
+                    <PatternsTable
+                        patterns={Rc::clone(&self.patterns)}
+                        patterns_by_tag={Rc::clone(&self.patterns_by_tag)}
+                        {timestamps}
+                        on_pattern_selected={link.callback(|(template, tag)| Msg::FilterByPattern(template, tag))}
+                    />
+                },
                 Err(error) => self.view_parsing_error(error),
             },
             Tab::Raw => html! {
@@ -72,34 +281,114 @@ impl File {
                         size={ButtonSize::Medium}
                         icon={classes!("fas", "fa-download")}
                         text="Download"
-                        content={Rc::clone(&self.text)}
-                        filename={format!(
-                            "{}-{}{}.txt",
-                            self.remote_object.platform(),
-                            self.remote_object.key(),
-                            self.name.as_ref()
-                                .map(|name| format!(
-                                    "-{}-{}",
-                                    name.app_id,
-                                    name.file_time.format("%F-%H-%M-%S-%3f-%Z")
-                                ))
-                                .unwrap_or_else(|| "".to_owned())
-                        ).to_lowercase()}
+                        content={
+                            let text = Rc::clone(&self.text);
+                            Callback::from(move |_| text.to_string())
+                        }
+                        filename={format!("{}.txt", self.export_filename_stem())}
                     />
 
+                    {
+                        match &self.parsed {
+                            Ok((format, parsed)) => html! {
+                                <DownloadButton
+                                    classes={classes!("rounded-2xl", "ml-2")}
+                                    size={ButtonSize::Medium}
+                                    icon={classes!("fas", "fa-file-export")}
+                                    text="Download re-exported"
+                                    content={
+                                        let (format, parsed) = (*format, Rc::clone(parsed));
+                                        Callback::from(move |_| format.write(&parsed))
+                                    }
+                                    filename={format!("{}-reexported.txt", self.export_filename_stem())}
+                                />
+                            },
+                            Err(_) => html! {},
+                        }
+                    }
+
                     <CodeBlock text={Rc::clone(&self.text)}/>
                 </>
             },
         };
 
+        let warnings = if self.warnings.is_empty() {
+            html! {}
+        } else {
+            html! {
+                <Message heading="Warning" classes={classes!("mb-8")}>
+                    { for self.warnings.iter().map(|warning| html! { <p>{ warning }</p> }) }
+                </Message>
+            }
+        };
+
         html! {
             <>
+                { warnings }
                 { title }
                 { content }
             </>
         }
     }
 
+    /// Drawer with details of the selected log entries, if they're still in the file.
+    pub fn view_details(
+        &self,
+        selection: &EntrySelection,
+        timezone: DisplayTimezone,
+        link: &Scope<Model>,
+    ) -> Html {
+        let section = match &self.parsed {
+            Ok((_, parsed)) => details::find_section(&parsed.logs, &selection.section),
+            Err(_) => None,
+        };
+
+        match section {
+            Some(section) => details::view(
+                section,
+                selection,
+                &self.report_source(),
+                &self.timestamps(timezone),
+                link,
+            ),
+            None => html! {},
+        }
+    }
+
+    /// Where the file is from, for reports of its log entries.
+    fn report_source(&self) -> String {
+        let url = self.remote_object.debuglogs_url();
+
+        match &self.name {
+            Some(ArchiveFilename::Log(name)) => format!(
+                "{} log from {} in {url}",
+                name.app_id,
+                name.file_time.format("%F %T %Z")
+            ),
+            Some(ArchiveFilename::Other(name)) => format!("{name} in {url}"),
+            None => url,
+        }
+    }
+
+    /// File name (without extension) to use for files downloaded from this one.
+    fn export_filename_stem(&self) -> String {
+        format!(
+            "{}-{}{}",
+            self.remote_object.platform(),
+            self.remote_object.key(),
+            match &self.name {
+                Some(ArchiveFilename::Log(name)) => format!(
+                    "-{}-{}",
+                    name.app_id,
+                    name.file_time.format("%F-%H-%M-%S-%3f-%Z")
+                ),
+                Some(ArchiveFilename::Other(name)) => format!("-{}", name.replace('/', "-")),
+                None => "".to_owned(),
+            }
+        )
+        .to_lowercase()
+    }
+
     fn view_parsing_error(&self, error: &anyhow::Error) -> Html {
         let notice = "You can still view the raw log by switching to the corresponding tab below"
             .to_owned()
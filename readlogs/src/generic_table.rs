@@ -0,0 +1,140 @@
+use std::cmp::Ordering;
+
+use crate::parsers::GenericTable;
+
+/// Compares cells as numbers if both are, otherwise as case-insensitive text, with numbers first.
+pub fn compare_cells(a: &str, b: &str) -> Ordering {
+    match (a.trim().parse::<f64>(), b.trim().parse::<f64>()) {
+        (Ok(a), Ok(b)) => a.total_cmp(&b),
+        (Ok(_), Err(_)) => Ordering::Less,
+        (Err(_), Ok(_)) => Ordering::Greater,
+        (Err(_), Err(_)) => a.to_lowercase().cmp(&b.to_lowercase()),
+    }
+}
+
+/// The field quoted if it contains a delimiter, quote or line break, as RFC 4180 requires.
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_owned()
+    }
+}
+
+fn csv_line(fields: &[String]) -> String {
+    fields
+        .iter()
+        .map(|field| csv_field(field))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+impl GenericTable {
+    /// Rows whose cells contain the filter of their column (case-insensitive), sorted by the
+    /// column if there is one, in ascending order or not.
+    pub fn displayed_rows(
+        &self,
+        filters: &[String],
+        sort: Option<(usize, bool)>,
+    ) -> Vec<&Vec<String>> {
+        let filters = filters
+            .iter()
+            .map(|filter| filter.to_lowercase())
+            .collect::<Vec<_>>();
+
+        let mut rows = self
+            .rows
+            .iter()
+            .filter(|row| {
+                filters.iter().enumerate().all(|(column, filter)| {
+                    filter.is_empty()
+                        || matches!(row.get(column), Some(cell) if cell.to_lowercase().contains(filter))
+                })
+            })
+            .collect::<Vec<_>>();
+
+        if let Some((column, ascending)) = sort {
+            rows.sort_by(|a, b| {
+                let cell = |row: &Vec<String>| row.get(column).cloned().unwrap_or_default();
+                let ordering = compare_cells(&cell(a), &cell(b));
+
+                if ascending {
+                    ordering
+                } else {
+                    ordering.reverse()
+                }
+            });
+        }
+
+        rows
+    }
+
+    /// The header and the rows as comma-separated values.
+    pub fn to_csv(&self, rows: &[&Vec<String>]) -> String {
+        [&self.header]
+            .into_iter()
+            .chain(rows.iter().copied())
+            .map(|row| csv_line(row) + "\r\n")
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+
+    use super::*;
+
+    fn test_table() -> GenericTable {
+        let row = |cells: [&str; 2]| cells.map(str::to_owned).to_vec();
+
+        GenericTable {
+            header: row(["name", "size"]),
+            rows: vec![
+                row(["messages", "100"]),
+                row(["recipients", "9"]),
+                row(["Groups", "unknown"]),
+                row(["threads", "20.5"]),
+            ],
+        }
+    }
+
+    #[test_case("9", "100" => Ordering::Less; "numbers")]
+    #[test_case("-1.5", "-2" => Ordering::Greater; "negative numbers")]
+    #[test_case("abc", "ABD" => Ordering::Less; "text")]
+    #[test_case("abc", "12" => Ordering::Greater; "numbers first")]
+    fn compare_cells_ok(a: &str, b: &str) -> Ordering {
+        compare_cells(a, b)
+    }
+
+    #[test_case(&[], None => vec!["messages", "recipients", "Groups", "threads"]; "unchanged")]
+    #[test_case(&[], Some((1, true)) => vec!["recipients", "threads", "messages", "Groups"]; "numeric")]
+    #[test_case(&[], Some((0, false)) => vec!["threads", "recipients", "messages", "Groups"]; "descending")]
+    #[test_case(&["s", "00"], None => vec!["messages"]; "filters")]
+    #[test_case(&["", "UNKNOWN"], None => vec!["Groups"]; "case-insensitive filter")]
+    fn displayed_rows(filters: &[&str], sort: Option<(usize, bool)>) -> Vec<String> {
+        let filters = filters
+            .iter()
+            .map(|filter| filter.to_string())
+            .collect::<Vec<_>>();
+
+        test_table()
+            .displayed_rows(&filters, sort)
+            .into_iter()
+            .map(|row| row[0].clone())
+            .collect()
+    }
+
+    #[test]
+    fn to_csv() {
+        let table = GenericTable {
+            header: vec!["name".to_owned(), "note".to_owned()],
+            rows: vec![vec!["a".to_owned(), "says \"hi\", twice".to_owned()]],
+        };
+
+        assert_eq!(
+            table.to_csv(&table.rows.iter().collect::<Vec<_>>()),
+            "name,note\r\na,\"says \"\"hi\"\", twice\"\r\n"
+        );
+    }
+}
@@ -0,0 +1,149 @@
+use std::rc::Rc;
+
+use regex::Regex;
+use yew::prelude::*;
+
+use crate::SearchQuery;
+
+/// Highlights matches of the active search query, whose pattern is compiled once when the query
+/// is applied. Matches in log rows are numbered by where they are among the matches of all
+/// displayed entries (see `EntryPosition`), so that they can be navigated between.
+#[derive(Debug, Clone, Default)]
+pub struct Highlighter {
+    pattern: Option<Rc<Regex>>,
+    current_match: usize,
+    cursor: Option<usize>,
+}
+
+impl Highlighter {
+    pub fn new(query: &SearchQuery) -> Self {
+        Self {
+            pattern: query.pattern().ok().flatten().map(Rc::new),
+            ..Default::default()
+        }
+    }
+
+    pub fn with_current_match(self, current_match: usize) -> Self {
+        Self {
+            current_match,
+            ..self
+        }
+    }
+
+    pub fn with_cursor(self, cursor: Option<usize>) -> Self {
+        Self { cursor, ..self }
+    }
+
+    pub fn match_id(index: usize) -> String {
+        format!("match-{index}")
+    }
+
+    pub fn row_id(index: usize) -> String {
+        format!("row-{index}")
+    }
+
+    /// Whether the row cursor is on the row with the index.
+    pub fn is_cursor(&self, row: usize) -> bool {
+        self.cursor == Some(row)
+    }
+
+    /// Whether there is a query whose matches are highlighted.
+    pub fn is_active(&self) -> bool {
+        self.pattern.is_some()
+    }
+
+    /// Whether `text` should be displayed. Always `true` if there is no query.
+    pub fn is_match(&self, text: &str) -> bool {
+        match &self.pattern {
+            Some(pattern) => pattern.is_match(text),
+            None => true,
+        }
+    }
+
+    /// Number of non-empty matches in `text` that [`Highlighter::view`] would highlight.
+    pub fn count(&self, text: &str) -> usize {
+        match &self.pattern {
+            Some(pattern) => pattern
+                .find_iter(text)
+                .filter(|m| m.start() != m.end())
+                .count(),
+            None => 0,
+        }
+    }
+
+    /// Whether the current match is among the `count` matches numbered from `first_match`.
+    pub fn is_current_among(&self, first_match: usize, count: usize) -> bool {
+        (first_match..first_match + count).contains(&self.current_match)
+    }
+
+    /// Highlights the matches in `text`, numbering them from `first_match` if they can be
+    /// navigated between.
+    pub fn view(&self, text: &str, first_match: Option<usize>) -> Html {
+        self.view_with(text, first_match, |text| html! { text })
+    }
+
+    /// Like [`Highlighter::view`], but displays the text around (and in) matches with `view_text`.
+    pub fn view_with(
+        &self,
+        text: &str,
+        first_match: Option<usize>,
+        view_text: impl Fn(&str) -> Html,
+    ) -> Html {
+        let pattern = match &self.pattern {
+            Some(pattern) => pattern,
+            None => return view_text(text),
+        };
+
+        let mut parts = vec![];
+        let mut last_end = 0;
+
+        let matches = pattern.find_iter(text).filter(|m| m.start() != m.end());
+
+        for (offset, m) in matches.enumerate() {
+            let index = first_match.map(|first_match| first_match + offset);
+
+            let classes = if index == Some(self.current_match) {
+                classes!("bg-orange-400", "dark:bg-orange-600", "text-inherit")
+            } else {
+                classes!("bg-yellow-200", "dark:bg-yellow-700", "text-inherit")
+            };
+
+            parts.push(view_text(&text[last_end..m.start()]));
+            parts.push(html! {
+                <mark id={index.map(Self::match_id)} class={classes}>{ view_text(m.as_str()) }</mark>
+            });
+
+            last_end = m.end();
+        }
+
+        parts.push(view_text(&text[last_end..]));
+
+        html! { for parts }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+
+    use super::*;
+
+    fn test_query(string: &str, regex: bool) -> SearchQuery {
+        SearchQuery {
+            string: string.to_owned(),
+            regex,
+            ..Default::default()
+        }
+    }
+
+    #[test_case("", false, "Anything" => (true, 0); "empty query")]
+    #[test_case("abc", false, "ABC abc aBc" => (true, 3); "case insensitive substring")]
+    #[test_case("a.c", false, "abc" => (false, 0); "substring is not a regex")]
+    #[test_case("a.c", false, "a.c A.C" => (true, 2); "substring with special characters")]
+    #[test_case("job-\\d+", true, "job-1 job-23 job-x" => (true, 2); "regex")]
+    #[test_case("x*", true, "abc" => (true, 0); "regex with empty matches")]
+    fn is_match_and_count(string: &str, regex: bool, text: &str) -> (bool, usize) {
+        let highlighter = Highlighter::new(&test_query(string, regex));
+        (highlighter.is_match(text), highlighter.count(text))
+    }
+}
@@ -0,0 +1,530 @@
+use std::{borrow::Cow, collections::BTreeSet, rc::Rc};
+
+use yew::{html::Scope, prelude::*};
+
+use crate::{
+    components::*,
+    metrics,
+    parsers::{section_path, Content, GenericTable, InfoEntry, LogFormat, Section, Value},
+    Model, Msg,
+};
+
+/// How the sections of the information tab are displayed.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct InformationOptions {
+    /// Only sections, keys and values containing this (case-insensitively) are displayed.
+    pub filter: String,
+    /// Paths of the sections whose content is hidden.
+    pub collapsed: BTreeSet<String>,
+}
+
+impl InformationOptions {
+    pub fn toggle_collapsed(&mut self, path: String) {
+        if !self.collapsed.remove(&path) {
+            self.collapsed.insert(path);
+        }
+    }
+
+    /// Whether the section's content is hidden, which it never is while it's being filtered.
+    fn is_collapsed(&self, path: &str) -> bool {
+        self.filter.is_empty() && self.collapsed.contains(path)
+    }
+}
+
+/// Consecutive entries of a section that are displayed together.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EntryGroup<'a> {
+    /// Rows of a key-value table, with an enabled column if any of them has `enabled`.
+    Keys {
+        entries: Vec<&'a InfoEntry>,
+        enabled_column: bool,
+    },
+    /// Lines of text in a code block.
+    Text(Vec<&'a InfoEntry>),
+    Single(&'a InfoEntry),
+}
+
+impl EntryGroup<'_> {
+    /// Renders the entries of the section with the path.
+    fn view(&self, path: &str) -> Html {
+        match self {
+            EntryGroup::Keys {
+                entries,
+                enabled_column,
+            } => html! {
+                <Table classes={classes!("font-mono", "text-sm")}>
+                    <tbody>
+                        { for entries.iter().map(|entry| entry.view_row(*enabled_column)) }
+                    </tbody>
+                </Table>
+            },
+            EntryGroup::Text(entries) => html! {
+                <CodeBlock>
+                    { for entries.iter().map(|entry| entry.view(path)) }
+                </CodeBlock>
+            },
+            EntryGroup::Single(entry) => entry.view(path),
+        }
+    }
+}
+
+/// Groups consecutive key-value entries into tables and consecutive lines of text into blocks.
+pub fn group_entries(entries: &[InfoEntry]) -> Vec<EntryGroup<'_>> {
+    let mut groups = Vec::<EntryGroup>::new();
+
+    for entry in entries {
+        match (groups.last_mut(), entry) {
+            (
+                Some(EntryGroup::Keys {
+                    entries,
+                    enabled_column,
+                }),
+                InfoEntry::KeyValue(_, _) | InfoEntry::KeyEnabledValue(_, _, _),
+            ) => {
+                *enabled_column |= entry.is_key_enabled_value();
+                entries.push(entry);
+            }
+            (_, InfoEntry::KeyValue(_, _) | InfoEntry::KeyEnabledValue(_, _, _)) => {
+                groups.push(EntryGroup::Keys {
+                    entries: vec![entry],
+                    enabled_column: entry.is_key_enabled_value(),
+                });
+            }
+            (Some(EntryGroup::Text(entries)), InfoEntry::Generic(_)) => entries.push(entry),
+            (_, InfoEntry::Generic(_)) => groups.push(EntryGroup::Text(vec![entry])),
+            _ => groups.push(EntryGroup::Single(entry)),
+        }
+    }
+
+    groups
+}
+
+/// The section's path with everything but letters and digits replaced, to use in ids and file
+/// names.
+pub fn section_slug(path: &str) -> String {
+    path.chars()
+        .map(|c| {
+            if c.is_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '-'
+            }
+        })
+        .collect()
+}
+
+fn section_id(path: &str) -> String {
+    format!("information-{}", section_slug(path))
+}
+
+fn contains(text: &str, needle: &str) -> bool {
+    text.to_lowercase().contains(needle)
+}
+
+impl Value {
+    /// Whether the value or any of its buckets contains the lowercase needle.
+    pub fn contains(&self, needle: &str) -> bool {
+        match self {
+            Value::Generic(value) => contains(value, needle),
+            Value::BucketedFlag(buckets) => buckets.iter().any(|bucket| {
+                contains(&bucket.country_code, needle) || contains(&bucket.value, needle)
+            }),
+        }
+    }
+}
+
+impl InfoEntry {
+    /// The entry with only what contains the lowercase needle, if anything does.
+    pub fn filtered(&self, needle: &str) -> Option<Self> {
+        let matches = match self {
+            InfoEntry::KeyValue(key, value) => contains(key, needle) || value.contains(needle),
+            InfoEntry::KeyEnabledValue(key, _, value) => {
+                contains(key, needle) || matches!(value, Some(value) if value.contains(needle))
+            }
+            InfoEntry::RemoteObject(remote_object) => {
+                contains(&remote_object.debuglogs_url(), needle)
+            }
+            InfoEntry::ExplicitNone => false,
+            InfoEntry::GenericTable(table) => {
+                if table.header.iter().any(|item| contains(item, needle)) {
+                    return Some(self.clone());
+                }
+
+                let rows = table
+                    .rows
+                    .iter()
+                    .filter(|row| row.iter().any(|item| contains(item, needle)))
+                    .cloned()
+                    .collect::<Vec<_>>();
+
+                return (!rows.is_empty()).then(|| {
+                    InfoEntry::GenericTable(Rc::new(GenericTable {
+                        header: table.header.clone(),
+                        rows,
+                    }))
+                });
+            }
+            InfoEntry::Generic(text) => contains(text, needle),
+        };
+
+        matches.then(|| self.clone())
+    }
+}
+
+impl Section<InfoEntry> {
+    /// The section with only the entries and subsections matching the filter, if any do. All of
+    /// it matches if its name does.
+    pub fn filtered(&self, filter: &str) -> Option<Self> {
+        let needle = filter.to_lowercase();
+
+        if contains(&self.name, &needle) {
+            return Some(self.clone());
+        }
+
+        let section = Self {
+            name: self.name.clone(),
+            content: self
+                .content
+                .iter()
+                .filter_map(|entry| entry.filtered(&needle))
+                .collect(),
+            subsections: self
+                .subsections
+                .iter()
+                .filter_map(|subsection| subsection.filtered(filter))
+                .collect(),
+        };
+
+        (!section.content.is_empty() || !section.subsections.is_empty()).then_some(section)
+    }
+
+    /// Number of entries in the section and its subsections.
+    pub fn entry_count(&self) -> usize {
+        self.content.len()
+            + self
+                .subsections
+                .iter()
+                .map(Self::entry_count)
+                .sum::<usize>()
+    }
+
+    fn table_of_contents(&self, parent: Option<&str>) -> Html {
+        let path = section_path(parent, &self.name);
+        let raw = parent.is_some();
+
+        html! {
+            <ul>
+                <li>
+                    <Link href={format!("#{}", section_id(&path))} new_tab={false}>
+                        <Title
+                            level={TitleLevel::H4}
+                            text={self.name.clone()}
+                            meta={self.entry_count().to_string()}
+                            {raw}
+                            capitalize={!raw}
+                        />
+                    </Link>
+
+                    { for self.subsections.iter().map(|subsection| subsection.table_of_contents(Some(&path))) }
+                </li>
+            </ul>
+        }
+    }
+
+    pub fn view(
+        &self,
+        level: TitleLevel,
+        parent: Option<&str>,
+        options: &InformationOptions,
+        link: &Scope<Model>,
+    ) -> Html {
+        let path = section_path(parent, &self.name);
+        let collapsed = options.is_collapsed(&path);
+        let raw = level > TitleLevel::H2;
+
+        let title = html! {
+            <div
+                class="flex items-center gap-x-2 cursor-pointer select-none"
+                onclick={link.callback({
+                    let path = path.clone();
+                    move |_| Msg::ToggleInformationSection(path.clone())
+                })}
+            >
+                <Icon icon={classes!("fas", if collapsed { "fa-chevron-right" } else { "fa-chevron-down" })}/>
+                <Title
+                    {level}
+                    text={self.name.clone()}
+                    id={section_id(&path)}
+                    {raw}
+                    capitalize={!raw}
+                />
+            </div>
+        };
+
+        if collapsed {
+            return title;
+        }
+
+        if let Some(metrics) = metrics::metrics(self) {
+            return html! {
+                <>
+                    { title }
+                    <MetricsChart metrics={Rc::new(metrics)} />
+                </>
+            };
+        }
+
+        let groups = group_entries(&self.content);
+
+        let full_content = if self.content.is_empty() && self.subsections.is_empty() {
+            html! {
+                <p><i>{ "No content or subsections" }</i></p>
+            }
+        } else {
+            html! {
+                <>
+                    { for groups.iter().map(|group| group.view(&path)) }
+                    {
+                        for self.subsections.iter().map(|section| {
+                            section.view(level.incremented().unwrap(), Some(&path), options, link)
+                        })
+                    }
+                </>
+            }
+        };
+
+        html! {
+            <>
+                { title }
+                { full_content }
+            </>
+        }
+    }
+}
+
+impl Content {
+    pub fn view_information(
+        &self,
+        format: &dyn LogFormat,
+        options: &InformationOptions,
+        link: &Scope<Model>,
+    ) -> Html {
+        if self.information.is_empty() {
+            let text = if format.has_information() {
+                "This file doesn't seem to contain any dedicated information sections.".to_owned()
+            } else {
+                format!(
+                    "Signal {} debug logs don't contain any dedicated information sections.",
+                    format.name()
+                )
+            };
+
+            return html! {
+                <Message {text} />
+            };
+        }
+
+        // Only the sections that are filtered need to be copied.
+        let sections: Cow<[Section<InfoEntry>]> = if options.filter.is_empty() {
+            Cow::Borrowed(&self.information)
+        } else {
+            Cow::Owned(
+                self.information
+                    .iter()
+                    .filter_map(|section| section.filtered(&options.filter))
+                    .collect(),
+            )
+        };
+
+        let all_paths = self
+            .information
+            .iter()
+            .map(|section| section.name.clone())
+            .collect::<BTreeSet<_>>();
+        let all_collapsed = options.collapsed.is_superset(&all_paths);
+
+        let sections_html = if sections.is_empty() {
+            html! {
+                <Message text={format!("No sections, keys or values contain \"{}\".", options.filter)} />
+            }
+        } else {
+            html! {
+                <>
+                    { for sections.iter().map(|section| section.table_of_contents(None)) }
+                    <hr/>
+                    { for sections.iter().map(|section| section.view(TitleLevel::H2, None, options, link)) }
+                </>
+            }
+        };
+
+        html! {
+            <>
+                <div class="flex not-prose mb-4">
+                    <Input
+                        classes={classes!("rounded-l-2xl", "border", "py-1")}
+                        placeholder="Filter sections, keys and values"
+                        value={options.filter.clone()}
+                        on_change={link.callback(Msg::UpdateInformationFilter)}
+                    />
+                    <Button
+                        icon={classes!("fas", if all_collapsed { "fa-angles-down" } else { "fa-angles-up" })}
+                        text={if all_collapsed { "Expand all" } else { "Collapse all" }}
+                        on_click={link.callback(move |_| {
+                            Msg::UpdateInformationCollapsed(if all_collapsed { BTreeSet::new() } else { all_paths.clone() })
+                        })}
+                    />
+                </div>
+
+                { sections_html }
+            </>
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+
+    use super::*;
+    use crate::parsers::Bucket;
+
+    fn test_section() -> Section<InfoEntry> {
+        Section {
+            name: "FEATURE FLAGS".to_owned(),
+            content: vec![
+                InfoEntry::KeyValue(
+                    "android.payments".to_owned(),
+                    Value::Generic("true".to_owned()),
+                ),
+                InfoEntry::KeyValue(
+                    "android.donations".to_owned(),
+                    Value::BucketedFlag(vec![Bucket {
+                        country_code: "49".to_owned(),
+                        value: "1000000".to_owned(),
+                    }]),
+                ),
+            ],
+            subsections: vec![Section {
+                name: "Tables".to_owned(),
+                content: vec![InfoEntry::GenericTable(Rc::new(GenericTable {
+                    header: vec!["Name".to_owned(), "Size".to_owned()],
+                    rows: vec![
+                        vec!["Payments".to_owned(), "10".to_owned()],
+                        vec!["Messages".to_owned(), "20".to_owned()],
+                    ],
+                }))],
+                subsections: vec![],
+            }],
+        }
+    }
+
+    #[test_case("flags" => Some(3); "section name")]
+    #[test_case("PAYMENTS" => Some(2); "key and table row")]
+    #[test_case("49" => Some(1); "bucket")]
+    #[test_case("size" => Some(1); "table header")]
+    #[test_case("nothing" => None; "no match")]
+    fn filtered_entry_count(filter: &str) -> Option<usize> {
+        test_section()
+            .filtered(filter)
+            .map(|section| section.entry_count())
+    }
+
+    #[test]
+    fn filtered_table_rows() {
+        let section = test_section().filtered("messages").unwrap();
+
+        assert!(section.content.is_empty());
+        assert_eq!(
+            section.subsections[0].content,
+            vec![InfoEntry::GenericTable(Rc::new(GenericTable {
+                header: vec!["Name".to_owned(), "Size".to_owned()],
+                rows: vec![vec!["Messages".to_owned(), "20".to_owned()]],
+            }))]
+        );
+    }
+
+    fn key_value(key: &str) -> InfoEntry {
+        InfoEntry::KeyValue(key.to_owned(), Value::Generic("value".to_owned()))
+    }
+
+    fn key_enabled_value(key: &str) -> InfoEntry {
+        InfoEntry::KeyEnabledValue(key.to_owned(), true, None)
+    }
+
+    fn generic(text: &str) -> InfoEntry {
+        InfoEntry::Generic(text.to_owned())
+    }
+
+    #[test]
+    fn group_entries_mixed() {
+        let entries = vec![
+            key_value("a"),
+            key_enabled_value("b"),
+            generic("text"),
+            generic("more text"),
+            InfoEntry::ExplicitNone,
+            key_value("c"),
+        ];
+
+        assert_eq!(
+            group_entries(&entries),
+            vec![
+                EntryGroup::Keys {
+                    entries: vec![&entries[0], &entries[1]],
+                    enabled_column: true,
+                },
+                EntryGroup::Text(vec![&entries[2], &entries[3]]),
+                EntryGroup::Single(&entries[4]),
+                EntryGroup::Keys {
+                    entries: vec![&entries[5]],
+                    enabled_column: false,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn group_entries_tables_stay_separate() {
+        let table = InfoEntry::GenericTable(Rc::new(GenericTable {
+            header: vec![],
+            rows: vec![],
+        }));
+        let entries = vec![table.clone(), table];
+
+        assert_eq!(
+            group_entries(&entries),
+            vec![
+                EntryGroup::Single(&entries[0]),
+                EntryGroup::Single(&entries[1])
+            ]
+        );
+    }
+
+    #[test_case(None, "Flags" => "information-flags"; "top level")]
+    #[test_case(Some("Database"), "Table sizes" => "information-database-table-sizes"; "subsection")]
+    fn section_id_ok(parent: Option<&str>, name: &str) -> String {
+        section_id(&section_path(parent, name))
+    }
+
+    #[test_case("Table sizes" => "table-sizes"; "spaces")]
+    #[test_case("Database/Table sizes" => "database-table-sizes"; "subsection")]
+    #[test_case("JOBS: Constraints (2)" => "jobs--constraints--2-"; "punctuation")]
+    fn section_slug_ok(path: &str) -> String {
+        section_slug(path)
+    }
+
+    #[test]
+    fn toggle_collapsed() {
+        let mut options = InformationOptions::default();
+
+        options.toggle_collapsed("Flags".to_owned());
+        assert!(options.is_collapsed("Flags"));
+
+        options.filter = "payments".to_owned();
+        assert!(!options.is_collapsed("Flags"));
+
+        options.toggle_collapsed("Flags".to_owned());
+        options.filter.clear();
+        assert!(!options.is_collapsed("Flags"));
+    }
+}
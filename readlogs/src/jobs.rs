@@ -0,0 +1,404 @@
+use std::collections::{HashMap, HashSet};
+
+use chrono::prelude::*;
+use derive_more::Display;
+use regex::Regex;
+use strum_macros::EnumIter;
+
+use crate::parsers::{InfoEntry, LogEntry, Section, Value};
+
+const JOBS_SECTION_NAME: &str = "JOBS";
+const JOBS_SUBSECTION_NAME: &str = "Jobs";
+const CONSTRAINTS_SUBSECTION_NAME: &str = "Constraints";
+const DEPENDENCIES_SUBSECTION_NAME: &str = "Dependencies";
+
+#[derive(Debug, Display, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum JobState {
+    Running,
+    Retrying,
+    Pending,
+}
+
+/// A job from the Signal Android `JobManager` dump.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Job {
+    pub id: String,
+    pub factory_key: Option<String>,
+    pub queue_key: Option<String>,
+    pub state: JobState,
+    pub run_attempt: Option<u32>,
+    pub max_attempts: Option<i64>,
+    pub next_run_time: Option<DateTime<Utc>>,
+    pub constraints: Vec<String>,
+    /// Ids of the jobs that have to finish before this one.
+    pub dependencies: Vec<String>,
+    /// Number of log entries that mention the job's id.
+    pub mentions: usize,
+}
+
+#[derive(Debug, Display, Clone, Copy, PartialEq, Eq, EnumIter)]
+pub enum JobColumn {
+    Id,
+    #[display(fmt = "Factory key")]
+    FactoryKey,
+    Queue,
+    State,
+    #[display(fmt = "Run attempts")]
+    RunAttempts,
+    #[display(fmt = "Next run")]
+    NextRun,
+    Dependencies,
+    Mentions,
+}
+
+impl Job {
+    fn new(section: &Section<InfoEntry>) -> Self {
+        let value = |key: &str| find_value(section, key);
+        let number = |key: &str| value(key).and_then(|value| value.parse::<i64>().ok());
+        let time = |millis: i64| Utc.timestamp_millis_opt(millis).single();
+
+        let run_attempt = value("runAttempt").and_then(|value| value.parse().ok());
+
+        let state = if value("isRunning").as_deref() == Some("true") {
+            JobState::Running
+        } else if matches!(run_attempt, Some(attempt) if attempt > 0) {
+            JobState::Retrying
+        } else {
+            JobState::Pending
+        };
+
+        let next_run_time = match (
+            number("nextRunAttemptTime"),
+            number("lastRunAttemptTime"),
+            number("nextBackoffInterval"),
+        ) {
+            (Some(next), _, _) if next > 0 => time(next),
+            (_, Some(last), Some(backoff)) if last > 0 => time(last + backoff),
+            _ => None,
+        };
+
+        Self {
+            id: section.name.clone(),
+            factory_key: value("factoryKey"),
+            queue_key: value("queueKey").filter(|queue_key| queue_key != "null"),
+            state,
+            run_attempt,
+            max_attempts: number("maxAttempts"),
+            next_run_time,
+            constraints: vec![],
+            dependencies: vec![],
+            mentions: 0,
+        }
+    }
+
+    pub fn compare_by(&self, other: &Self, column: JobColumn) -> std::cmp::Ordering {
+        match column {
+            JobColumn::Id => self.id.cmp(&other.id),
+            JobColumn::FactoryKey => self.factory_key.cmp(&other.factory_key),
+            JobColumn::Queue => self.queue_key.cmp(&other.queue_key),
+            JobColumn::State => self.state.cmp(&other.state),
+            JobColumn::RunAttempts => self.run_attempt.cmp(&other.run_attempt),
+            JobColumn::NextRun => self.next_run_time.cmp(&other.next_run_time),
+            JobColumn::Dependencies => self.dependencies.len().cmp(&other.dependencies.len()),
+            JobColumn::Mentions => self.mentions.cmp(&other.mentions),
+        }
+    }
+
+    /// Whether any of the job's fields contain the query (case-insensitive).
+    pub fn contains(&self, query: &str) -> bool {
+        let query = query.to_lowercase();
+
+        [
+            Some(&self.id),
+            self.factory_key.as_ref(),
+            self.queue_key.as_ref(),
+        ]
+        .into_iter()
+        .flatten()
+        .chain(&self.constraints)
+        .chain(&self.dependencies)
+        .any(|field| field.to_lowercase().contains(&query))
+            || self.state.to_string().to_lowercase().contains(&query)
+    }
+}
+
+fn find_value(section: &Section<InfoEntry>, key: &str) -> Option<String> {
+    section.content.iter().find_map(|entry| match entry {
+        InfoEntry::KeyValue(k, Value::Generic(value)) if k == key => Some(value.clone()),
+        _ => None,
+    })
+}
+
+/// Jobs from the `JOBS` information section, along with their constraints and dependencies.
+///
+/// `logs` are used to count how many times each job is mentioned.
+pub fn jobs(information: &[Section<InfoEntry>], logs: &[Section<LogEntry>]) -> Vec<Job> {
+    let subsections = match information
+        .iter()
+        .find(|section| section.name == JOBS_SECTION_NAME)
+    {
+        Some(section) => &section.subsections,
+        None => return vec![],
+    };
+
+    let subsection = |name: &str| {
+        subsections
+            .iter()
+            .find(|subsection| subsection.name == name)
+            .map_or(&[][..], |subsection| &subsection.subsections)
+    };
+
+    let mut jobs = subsection(JOBS_SUBSECTION_NAME)
+        .iter()
+        .map(Job::new)
+        .collect::<Vec<_>>();
+
+    for constraint in subsection(CONSTRAINTS_SUBSECTION_NAME) {
+        if let (Some(job), Some(factory_key)) = (
+            jobs.iter_mut().find(|job| job.id == constraint.name),
+            find_value(constraint, "factoryKey"),
+        ) {
+            job.constraints.push(factory_key);
+        }
+    }
+
+    for dependency in subsection(DEPENDENCIES_SUBSECTION_NAME) {
+        if let (Some(job), Some(depends_on)) = (
+            jobs.iter_mut().find(|job| job.id == dependency.name),
+            find_value(dependency, "dependsOnJobSpecId"),
+        ) {
+            job.dependencies.push(depends_on);
+        }
+    }
+
+    let mut mentions = HashMap::new();
+    count_mentions(
+        logs,
+        &Regex::new(r"JOB::[0-9A-Za-z*-]+").unwrap(),
+        &mut mentions,
+    );
+
+    for job in &mut jobs {
+        job.mentions = mentions.get(job.id.as_str()).copied().unwrap_or_default();
+    }
+
+    jobs
+}
+
+fn count_mentions<'a>(
+    sections: &'a [Section<LogEntry>],
+    job_id: &Regex,
+    counts: &mut HashMap<&'a str, usize>,
+) {
+    for section in sections {
+        for entry in &section.content {
+            for id in job_id.find_iter(&entry.message) {
+                *counts.entry(id.as_str()).or_insert(0) += 1;
+            }
+        }
+
+        count_mentions(&section.subsections, job_id, counts);
+    }
+}
+
+/// Jobs that depend on the job with the given id.
+pub fn dependents<'a>(jobs: &'a [Job], id: &'a str) -> impl Iterator<Item = &'a Job> {
+    jobs.iter()
+        .filter(move |job| job.dependencies.iter().any(|dependency| dependency == id))
+}
+
+/// Jobs to start the dependency trees from: the ones that others depend on but that don't depend
+/// on anything themselves, followed by one job of each cycle that can't be reached from those.
+pub fn dependency_roots(jobs: &[Job]) -> Vec<&Job> {
+    fn reach<'a>(jobs: &'a [Job], job: &'a Job, reached: &mut HashSet<&'a str>) {
+        if reached.insert(&job.id) {
+            dependents(jobs, &job.id).for_each(|dependent| reach(jobs, dependent, reached));
+        }
+    }
+
+    let mut roots = jobs
+        .iter()
+        .filter(|job| job.dependencies.is_empty() && dependents(jobs, &job.id).next().is_some())
+        .collect::<Vec<_>>();
+
+    let mut reached = HashSet::new();
+    roots
+        .iter()
+        .for_each(|root| reach(jobs, root, &mut reached));
+
+    for job in jobs {
+        if reached.contains(job.id.as_str()) {
+            continue;
+        }
+
+        let mut reachable = HashSet::new();
+        dependents(jobs, &job.id).for_each(|dependent| reach(jobs, dependent, &mut reachable));
+
+        if reachable.contains(job.id.as_str()) {
+            roots.push(job);
+            reached.extend(reachable);
+        }
+    }
+
+    roots
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_inline_section(name: &str, pairs: &[(&str, &str)]) -> Section<InfoEntry> {
+        Section {
+            name: name.to_owned(),
+            content: pairs
+                .iter()
+                .map(|(key, value)| {
+                    InfoEntry::KeyValue(key.to_string(), Value::Generic(value.to_string()))
+                })
+                .collect(),
+            subsections: vec![],
+        }
+    }
+
+    fn test_subsection(name: &str, subsections: Vec<Section<InfoEntry>>) -> Section<InfoEntry> {
+        Section {
+            name: name.to_owned(),
+            content: vec![],
+            subsections,
+        }
+    }
+
+    fn test_job(id: &str, dependencies: &[&str]) -> Job {
+        Job {
+            id: id.to_owned(),
+            factory_key: None,
+            queue_key: None,
+            state: JobState::Pending,
+            run_attempt: None,
+            max_attempts: None,
+            next_run_time: None,
+            constraints: vec![],
+            dependencies: dependencies.iter().map(|id| id.to_string()).collect(),
+            mentions: 0,
+        }
+    }
+
+    #[test]
+    fn dependency_roots_with_cycles() {
+        let jobs = vec![
+            test_job("a", &[]),
+            test_job("b", &["a", "c"]),
+            test_job("c", &["b"]),
+            test_job("d", &["e"]),
+            test_job("e", &["d"]),
+            test_job("f", &["e"]),
+            test_job("g", &[]),
+        ];
+
+        assert_eq!(
+            dependency_roots(&jobs)
+                .into_iter()
+                .map(|job| job.id.as_str())
+                .collect::<Vec<_>>(),
+            vec!["a", "d"]
+        );
+    }
+
+    #[test]
+    fn jobs_ok() {
+        let information = vec![test_subsection(
+            "JOBS",
+            vec![
+                test_subsection(
+                    "Jobs",
+                    vec![
+                        test_inline_section(
+                            "JOB::a",
+                            &[
+                                ("factoryKey", "PushProcessMessageJob"),
+                                ("queueKey", "null"),
+                                ("runAttempt", "2"),
+                                ("maxAttempts", "-1"),
+                                ("nextRunAttemptTime", "1674388800000"),
+                                ("isRunning", "false"),
+                            ],
+                        ),
+                        test_inline_section(
+                            "JOB::b",
+                            &[
+                                ("factoryKey", "AttachmentUploadJob"),
+                                ("queueKey", "QUEUE"),
+                                ("runAttempt", "0"),
+                                ("isRunning", "true"),
+                            ],
+                        ),
+                    ],
+                ),
+                test_subsection(
+                    "Constraints",
+                    vec![test_inline_section(
+                        "JOB::a",
+                        &[("factoryKey", "NetworkConstraint")],
+                    )],
+                ),
+                test_subsection(
+                    "Dependencies",
+                    vec![test_inline_section(
+                        "JOB::b",
+                        &[("dependsOnJobSpecId", "JOB::a")],
+                    )],
+                ),
+            ],
+        )];
+
+        let logs = vec![Section {
+            name: "LOGGER".to_owned(),
+            content: vec![LogEntry {
+                timestamp: "".to_owned(),
+                level: None,
+                meta: crate::parsers::PlatformMetadata::Desktop,
+                message: "[JOB::a][PushProcessMessageJob] Job failed".to_owned(),
+            }],
+            subsections: vec![],
+        }];
+
+        let jobs = jobs(&information, &logs);
+
+        assert_eq!(
+            jobs,
+            vec![
+                Job {
+                    id: "JOB::a".to_owned(),
+                    factory_key: Some("PushProcessMessageJob".to_owned()),
+                    queue_key: None,
+                    state: JobState::Retrying,
+                    run_attempt: Some(2),
+                    max_attempts: Some(-1),
+                    next_run_time: Some(Utc.with_ymd_and_hms(2023, 1, 22, 12, 0, 0).unwrap()),
+                    constraints: vec!["NetworkConstraint".to_owned()],
+                    dependencies: vec![],
+                    mentions: 1,
+                },
+                Job {
+                    id: "JOB::b".to_owned(),
+                    factory_key: Some("AttachmentUploadJob".to_owned()),
+                    queue_key: Some("QUEUE".to_owned()),
+                    state: JobState::Running,
+                    run_attempt: Some(0),
+                    max_attempts: None,
+                    next_run_time: None,
+                    constraints: vec![],
+                    dependencies: vec!["JOB::a".to_owned()],
+                    mentions: 0,
+                },
+            ]
+        );
+
+        assert_eq!(
+            dependents(&jobs, "JOB::a")
+                .map(|job| job.id.as_str())
+                .collect::<Vec<_>>(),
+            vec!["JOB::b"]
+        );
+    }
+}
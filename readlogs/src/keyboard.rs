@@ -0,0 +1,165 @@
+use gloo_events::EventListener;
+use wasm_bindgen::JsCast;
+use web_sys::{Element, KeyboardEvent};
+use yew::html::Scope;
+
+use crate::{LogLevel, Model, Msg};
+
+/// Keys and what they do, as listed in the shortcut overlay.
+pub const SHORTCUTS: [(&str, &str); 7] = [
+    ("/", "Focus the search input"),
+    (
+        "j / k",
+        "Move the row cursor to the next / previous displayed entry",
+    ),
+    ("n / N", "Go to the next / previous search match"),
+    (
+        "] / [",
+        "Go to the next / previous error (or more important) entry",
+    ),
+    ("1–6", "Switch tabs"),
+    ("?", "Show or hide the keyboard shortcuts"),
+    ("Esc", "Hide the keyboard shortcuts and the entry details"),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Shortcut {
+    FocusSearch,
+    NextRow,
+    PreviousRow,
+    NextMatch,
+    PreviousMatch,
+    NextProblem,
+    PreviousProblem,
+    /// Switches to the n-th tab applicable to the platform, counting from 0.
+    Tab(usize),
+    ToggleHelp,
+    HideHelp,
+}
+
+impl Shortcut {
+    /// Shortcut for the `KeyboardEvent.key` value, if there is one.
+    pub fn from_key(key: &str) -> Option<Self> {
+        Some(match key {
+            "/" => Self::FocusSearch,
+            "j" => Self::NextRow,
+            "k" => Self::PreviousRow,
+            "n" => Self::NextMatch,
+            "N" => Self::PreviousMatch,
+            "]" => Self::NextProblem,
+            "[" => Self::PreviousProblem,
+            "?" => Self::ToggleHelp,
+            "Escape" => Self::HideHelp,
+            _ => match key.parse::<usize>() {
+                Ok(number @ 1..=9) => Self::Tab(number - 1),
+                _ => return None,
+            },
+        })
+    }
+}
+
+/// Row cursor after moving it by one displayed entry, starting at the first one.
+pub fn move_cursor(cursor: Option<usize>, count: usize, forward: bool) -> Option<usize> {
+    if count == 0 {
+        return None;
+    }
+
+    Some(match (cursor, forward) {
+        (None, _) => 0,
+        (Some(cursor), true) => (cursor + 1).min(count - 1),
+        (Some(cursor), false) => cursor.saturating_sub(1).min(count - 1),
+    })
+}
+
+/// Closest displayed entry after (or before) the cursor with a level of at least `Error`.
+pub fn find_problem(levels: &[LogLevel], cursor: Option<usize>, forward: bool) -> Option<usize> {
+    let is_problem = |index: &usize| levels[*index] >= LogLevel::Error;
+
+    match (cursor, forward) {
+        (None, true) => (0..levels.len()).find(is_problem),
+        (Some(cursor), true) => (cursor + 1..levels.len()).find(is_problem),
+        (None, false) => (0..levels.len()).rev().find(is_problem),
+        (Some(cursor), false) => (0..cursor.min(levels.len())).rev().find(is_problem),
+    }
+}
+
+/// Whether typing into the element shouldn't trigger shortcuts.
+fn is_editable(element: &Element) -> bool {
+    matches!(
+        element.tag_name().to_lowercase().as_str(),
+        "input" | "select" | "textarea"
+    )
+}
+
+/// Sends shortcuts pressed anywhere on the page (except while typing) to the model.
+pub fn listen(link: Scope<Model>) -> Option<EventListener> {
+    let document = web_sys::window()?.document()?;
+
+    Some(EventListener::new(&document, "keydown", move |event| {
+        let event = event.unchecked_ref::<KeyboardEvent>();
+
+        if event.ctrl_key() || event.meta_key() || event.alt_key() {
+            return;
+        }
+
+        let editing = event
+            .target()
+            .and_then(|target| target.dyn_into::<Element>().ok())
+            .map(|element| is_editable(&element))
+            .unwrap_or(false);
+
+        match Shortcut::from_key(&event.key()) {
+            Some(shortcut) if !editing || shortcut == Shortcut::HideHelp => {
+                if shortcut != Shortcut::HideHelp {
+                    event.prevent_default();
+                }
+                link.send_message(Msg::Shortcut(shortcut));
+            }
+            _ => {}
+        }
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+
+    use super::*;
+
+    #[test_case("/" => Some(Shortcut::FocusSearch); "slash")]
+    #[test_case("N" => Some(Shortcut::PreviousMatch); "shifted")]
+    #[test_case("1" => Some(Shortcut::Tab(0)); "first tab")]
+    #[test_case("0" => None; "zero")]
+    #[test_case("x" => None; "other key")]
+    fn shortcut_from_key(key: &str) -> Option<Shortcut> {
+        Shortcut::from_key(key)
+    }
+
+    #[test_case(None, 3, true => Some(0); "starts at the first entry")]
+    #[test_case(Some(1), 3, true => Some(2); "forward")]
+    #[test_case(Some(2), 3, true => Some(2); "stops at the last entry")]
+    #[test_case(Some(0), 3, false => Some(0); "stops at the first entry")]
+    #[test_case(Some(5), 3, false => Some(2); "fewer entries than before")]
+    #[test_case(Some(1), 0, true => None; "no entries")]
+    fn move_cursor_ok(cursor: Option<usize>, count: usize, forward: bool) -> Option<usize> {
+        move_cursor(cursor, count, forward)
+    }
+
+    #[test_case(None, true => Some(1); "first")]
+    #[test_case(Some(1), true => Some(3); "next")]
+    #[test_case(Some(3), true => None; "none after")]
+    #[test_case(None, false => Some(3); "last")]
+    #[test_case(Some(3), false => Some(1); "previous")]
+    #[test_case(Some(1), false => None; "none before")]
+    fn find_problem_ok(cursor: Option<usize>, forward: bool) -> Option<usize> {
+        let levels = [
+            LogLevel::Info,
+            LogLevel::Error,
+            LogLevel::Warn,
+            LogLevel::Fatal,
+            LogLevel::Debug,
+        ];
+
+        find_problem(&levels, cursor, forward)
+    }
+}
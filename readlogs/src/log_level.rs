@@ -3,31 +3,26 @@ use strum_macros::{EnumIter, EnumString};
 use yew::prelude::*;
 use LogLevel::*;
 
-use crate::Platform::{self, *};
+use crate::parsers::LogFormat;
 
-#[derive(Debug, Display, Clone, Copy, PartialEq, Eq, PartialOrd, Hash, EnumString, EnumIter)]
+#[derive(
+    Debug, Display, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, EnumString, EnumIter,
+)]
 #[strum(ascii_case_insensitive)]
 pub enum LogLevel {
     /// Desktop
     Trace,
     /// Android, iOS
-    // `serialize = "verbose"` is included for parsing of `<select>`'s option.
-    #[strum(serialize = "V", serialize = "💙", serialize = "verbose")]
     Verbose,
     /// Android, iOS, Desktop
-    #[strum(serialize = "D", serialize = "💚", serialize = "debug")]
     Debug,
     /// Android, iOS, Desktop
-    #[strum(serialize = "I", serialize = "💛", serialize = "info")]
     Info,
     /// Android, iOS, Desktop
-    #[strum(serialize = "W", serialize = "🧡", serialize = "warn")]
     Warn,
     /// Android, iOS, Desktop
-    #[strum(serialize = "E", serialize = "❤️", serialize = "error")]
     Error,
     /// Android, Desktop
-    #[strum(serialize = "F", serialize = "fatal")]
     Fatal,
 }
 
@@ -38,14 +33,13 @@ impl Default for LogLevel {
 }
 
 impl LogLevel {
-    pub fn applicable_to_platform(&self, platform: Platform) -> bool {
-        matches!(
-            (self, platform),
-            (Trace, Desktop)
-                | (Verbose, Android | Ios)
-                | (Debug | Info | Warn | Error, Android | Ios | Desktop)
-                | (Fatal, Android | Desktop)
-        )
+    /// Whether the format maps any of its levels to this one, which every level is if the format
+    /// isn't known.
+    pub fn applicable_to(&self, format: Option<&dyn LogFormat>) -> bool {
+        match format {
+            Some(format) => format.levels().contains(self),
+            None => true,
+        }
     }
 
     pub fn color(&self) -> Classes {
@@ -1,21 +1,41 @@
+mod cache;
 pub mod components;
+mod deltas;
+mod details;
+mod download;
 mod file;
+mod generic_table;
+mod highlighter;
+mod information;
+mod jobs;
+mod keyboard;
 mod log_level;
+mod metrics;
 mod model;
 mod parsers;
+mod patterns;
 mod platform;
 pub mod post_processing;
 mod remote_object;
 mod rendered_log_section;
+mod rich_text;
+mod settings;
+mod summary;
+mod swimlanes;
+mod timeline;
+mod timezone;
 mod utils;
 mod view;
 
-pub use file::File;
+pub use deltas::{Anchor, DeltaColumns};
+pub use file::{File, FileStatus};
+pub use highlighter::Highlighter;
 pub use log_level::LogLevel;
 pub use model::*;
 pub use platform::Platform;
 pub use remote_object::{remote_object, RemoteObject};
 pub use rendered_log_section::RenderedLogSection;
+pub use timezone::{DisplayTimezone, TimestampFormatter};
 pub use utils::*;
 
 fn main() {
@@ -0,0 +1,270 @@
+use chrono::Duration;
+
+use crate::parsers::{InfoEntry, Section, Value};
+
+const LOCAL_METRICS_SECTION_NAME: &str = "LOCAL METRICS";
+
+/// Timings of an event, in milliseconds.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Percentiles {
+    pub p50: u64,
+    pub p90: u64,
+    pub p99: u64,
+}
+
+impl Percentiles {
+    pub fn iter(&self) -> impl Iterator<Item = (&'static str, u64)> {
+        [("p50", self.p50), ("p90", self.p90), ("p99", self.p99)].into_iter()
+    }
+}
+
+/// Timings of a metric as logged.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Timings {
+    Percentiles(Percentiles),
+    /// Key-value pairs of the metric, if any of its percentiles isn't a number.
+    Unparsed(Vec<(String, String)>),
+}
+
+/// A metric from the Signal Android `LOCAL METRICS` section, e.g. `cold-start-conversation-list`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Metric {
+    pub name: String,
+    pub count: Option<u64>,
+    pub timings: Timings,
+    /// Parts that the event is split into, e.g. `application-create`.
+    pub splits: Vec<Metric>,
+}
+
+impl Metric {
+    fn new(section: &Section<InfoEntry>) -> Self {
+        let pairs = section
+            .content
+            .iter()
+            .filter_map(|entry| match entry {
+                InfoEntry::KeyValue(key, Value::Generic(value)) => {
+                    Some((key.clone(), value.clone()))
+                }
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        let value = |key: &str| {
+            pairs
+                .iter()
+                .find_map(|(k, value)| (k == key).then(|| value.parse().ok()).flatten())
+        };
+
+        let percentiles = || {
+            Some(Percentiles {
+                p50: value("p50")?,
+                p90: value("p90")?,
+                p99: value("p99")?,
+            })
+        };
+
+        Self {
+            name: section.name.clone(),
+            count: value("count"),
+            timings: match percentiles() {
+                Some(percentiles) => Timings::Percentiles(percentiles),
+                None => Timings::Unparsed(pairs),
+            },
+            splits: section.subsections.iter().map(Metric::new).collect(),
+        }
+    }
+
+    /// Largest value of the metric and its splits.
+    pub fn max(&self) -> u64 {
+        let p99 = match &self.timings {
+            Timings::Percentiles(percentiles) => percentiles.p99,
+            Timings::Unparsed(_) => 0,
+        };
+
+        self.splits
+            .iter()
+            .map(Metric::max)
+            .chain([p99])
+            .max()
+            .unwrap_or_default()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Normal,
+    Warning,
+    Critical,
+}
+
+/// Timings (in milliseconds) from which values are highlighted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Thresholds {
+    pub warning: u64,
+    pub critical: u64,
+}
+
+impl Default for Thresholds {
+    fn default() -> Self {
+        Self {
+            warning: 1000,
+            critical: 5000,
+        }
+    }
+}
+
+impl Thresholds {
+    /// The thresholds with the warning one changed, raising the critical one to it if needed.
+    pub fn with_warning(self, warning: u64) -> Self {
+        Self {
+            warning,
+            critical: self.critical.max(warning),
+        }
+    }
+
+    /// The thresholds with the critical one changed, lowering the warning one to it if needed.
+    pub fn with_critical(self, critical: u64) -> Self {
+        Self {
+            warning: self.warning.min(critical),
+            critical,
+        }
+    }
+
+    pub fn severity(&self, value: u64) -> Severity {
+        if value >= self.critical {
+            Severity::Critical
+        } else if value >= self.warning {
+            Severity::Warning
+        } else {
+            Severity::Normal
+        }
+    }
+}
+
+pub fn format_milliseconds(value: u64) -> String {
+    crate::timeline::format_duration(Duration::milliseconds(value as i64))
+}
+
+/// Metrics from the section, if it's the local metrics one.
+pub fn metrics(section: &Section<InfoEntry>) -> Option<Vec<Metric>> {
+    (section.name == LOCAL_METRICS_SECTION_NAME)
+        .then(|| section.subsections.iter().map(Metric::new).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+
+    use super::*;
+
+    fn test_section(
+        name: &str,
+        pairs: &[(&str, &str)],
+        subsections: Vec<Section<InfoEntry>>,
+    ) -> Section<InfoEntry> {
+        Section {
+            name: name.to_owned(),
+            content: pairs
+                .iter()
+                .map(|(key, value)| {
+                    InfoEntry::KeyValue(key.to_string(), Value::Generic(value.to_string()))
+                })
+                .collect(),
+            subsections,
+        }
+    }
+
+    #[test]
+    fn metrics_ok() {
+        let section = test_section(
+            "LOCAL METRICS",
+            &[],
+            vec![test_section(
+                "cold-start-conversation-list",
+                &[
+                    ("count", "5"),
+                    ("p50", "3456"),
+                    ("p90", "4567"),
+                    ("p99", "4567"),
+                ],
+                vec![test_section(
+                    "data-loaded",
+                    &[("p50", "456"), ("p90", "789"), ("p99", "7890")],
+                    vec![],
+                )],
+            )],
+        );
+
+        let metrics = metrics(&section).unwrap();
+
+        assert_eq!(
+            metrics,
+            vec![Metric {
+                name: "cold-start-conversation-list".to_owned(),
+                count: Some(5),
+                timings: Timings::Percentiles(Percentiles {
+                    p50: 3456,
+                    p90: 4567,
+                    p99: 4567,
+                }),
+                splits: vec![Metric {
+                    name: "data-loaded".to_owned(),
+                    count: None,
+                    timings: Timings::Percentiles(Percentiles {
+                        p50: 456,
+                        p90: 789,
+                        p99: 7890,
+                    }),
+                    splits: vec![],
+                }],
+            }]
+        );
+
+        assert_eq!(metrics[0].max(), 7890);
+    }
+
+    #[test]
+    fn metrics_unparsed() {
+        let section = test_section(
+            "LOCAL METRICS",
+            &[],
+            vec![test_section(
+                "conversation-open",
+                &[("count", "1"), ("p50", "12"), ("p90", "?"), ("p99", "34")],
+                vec![],
+            )],
+        );
+
+        let metrics = metrics(&section).unwrap();
+
+        assert_eq!(
+            metrics[0].timings,
+            Timings::Unparsed(vec![
+                ("count".to_owned(), "1".to_owned()),
+                ("p50".to_owned(), "12".to_owned()),
+                ("p90".to_owned(), "?".to_owned()),
+                ("p99".to_owned(), "34".to_owned()),
+            ])
+        );
+        assert_eq!(metrics[0].max(), 0);
+    }
+
+    #[test]
+    fn metrics_other_section() {
+        assert_eq!(metrics(&test_section("PIN STATE", &[], vec![])), None);
+    }
+
+    #[test_case(999 => Severity::Normal; "normal")]
+    #[test_case(1000 => Severity::Warning; "warning")]
+    #[test_case(5000 => Severity::Critical; "critical")]
+    fn thresholds_severity(value: u64) -> Severity {
+        Thresholds::default().severity(value)
+    }
+
+    #[test_case(Thresholds::default().with_warning(2000) => Thresholds { warning: 2000, critical: 5000 }; "warning below critical")]
+    #[test_case(Thresholds::default().with_warning(10000) => Thresholds { warning: 10000, critical: 10000 }; "warning above critical")]
+    #[test_case(Thresholds::default().with_critical(500) => Thresholds { warning: 500, critical: 500 }; "critical below warning")]
+    fn thresholds_stay_ordered(thresholds: Thresholds) -> Thresholds {
+        thresholds
+    }
+}
@@ -1,43 +1,112 @@
 use std::{
-    collections::BTreeMap,
+    collections::{BTreeMap, BTreeSet},
+    future::Future,
     io::{self, Cursor},
     rc::Rc,
 };
 
 use anyhow::{anyhow, ensure, Context};
+use chrono::Duration;
 use derive_more::{Display, IsVariant};
+use gloo_events::EventListener;
+use regex::{Regex, RegexBuilder};
+use strum::IntoEnumIterator;
 use strum_macros::EnumIter;
-use web_sys::HtmlInputElement;
+use web_sys::{AbortController, HtmlInputElement, ScrollIntoViewOptions, ScrollLogicalPosition};
 use yew::prelude::*;
 use yewtil::NeqAssign;
 use zip::ZipArchive;
 
 use crate::{
-    parsers::{AppId, LogFilename},
+    cache::{self, RecentLog},
+    deltas::{Anchor, DeltaColumns},
+    details::EntrySelection,
+    download::{self, Progress},
+    information::InformationOptions,
+    keyboard::{self, Shortcut},
+    parsers::{AppId, ArchiveFilename, ColumnRole, LogFilename, LogFormat},
+    patterns::MessagePattern,
+    remote_object::{self, FetchMode},
+    settings::{self, HealthCheck},
+    swimlanes::SwimlaneOptions,
+    timezone::DisplayTimezone,
     *,
 };
 
 #[derive(Debug)]
 pub enum Msg {
     UpdateUrl(String),
+    DismissFoundRemoteObjects,
+    /// Opens every debug log that was found in the pasted text, each in its own document.
+    OpenFoundRemoteObjects,
     Start,
-    FetchError(anyhow::Error),
-    FinishedFetchText(String),
-    FinishedFetchBinary(Vec<u8>),
-    UpdateActiveFile(Rc<LogFilename>),
+    // Messages about loading a debug log start with the `Document::id` of its document.
+    FetchError(usize, anyhow::Error),
+    DownloadProgress(usize, Progress),
+    FinishedDownload(usize, Vec<u8>),
+    FinishedLoadFromCache(usize, Vec<u8>),
+    /// Extracts the files from a `zip` payload, caching it afterwards if `true`.
+    Decompress(usize, Vec<u8>, bool),
+    /// Parses a plain text payload, caching it afterwards if `true`.
+    ParseText(usize, Vec<u8>, bool),
+    ParseArchive(usize, Vec<(ArchiveFilename, Vec<u8>)>),
+    CancelFetch,
+    UpdateRecentLogs(Vec<RecentLog>),
+    OpenUrl(String),
+    UpdateRecentLogLabel(String, String),
+    RemoveRecentLog(String),
+    ClearCache,
+    UpdateFetchMode(FetchMode),
+    CheckHealth,
+    FinishedHealthCheck(HealthCheck),
+    SelectDocument(usize),
+    CloseDocument(usize),
+    NewDocument,
+    /// Applies the pending search query of the displayed document to every open one.
+    SearchAllDocuments,
+    UpdateActiveFile(ArchiveFilename),
     UpdateTab(Tab),
     UpdateMinLogLevel(String),
     UpdateQuery(String),
+    UpdateQueryRegex,
     UpdateUiExpanded,
     ApplySearchQuery,
+    PreviousMatch,
+    NextMatch,
+    /// Shows only log entries matching the regular expression.
+    FilterByRegex(String),
+    /// Shows only log entries whose message has the template, with the tag if there is one.
+    FilterByPattern(String, Option<String>),
+    /// Shows log entries with any tag again.
+    ClearQueryTag,
+    /// Shows log entries with any message again.
+    ClearQueryPattern,
+    UpdateGapThreshold(String),
+    ShowLane(String),
+    HideLane(String),
+    ToggleLanePinned(String),
+    UpdateInformationFilter(String),
+    /// Shows or hides the content of the information section with the path.
+    ToggleInformationSection(String),
+    UpdateInformationCollapsed(BTreeSet<String>),
+    UpdateTimezone(String),
+    UpdateDeltaColumns(String),
+    UpdateAnchor(Option<Anchor>),
+    SelectEntry(String, usize, bool),
+    CloseEntryDetails,
+    /// Shows only log entries mentioning the identifier.
+    Pivot(String),
+    /// Goes back to the search query from before the pivot at the index.
+    PopPivot(usize),
+    Shortcut(Shortcut),
 }
 
 #[derive(Debug)]
 pub enum Object {
     Single(File),
     Multiple {
-        files: BTreeMap<Rc<LogFilename>, File>,
-        active_filename: Rc<LogFilename>,
+        files: BTreeMap<ArchiveFilename, File>,
+        active_filename: ArchiveFilename,
     },
 }
 
@@ -45,7 +114,9 @@ pub enum Object {
 pub enum State {
     NoData,
     Error(anyhow::Error),
-    Fetching,
+    Downloading(Progress),
+    Decompressing,
+    Parsing,
     Ready(Object),
 }
 
@@ -54,13 +125,25 @@ impl PartialEq for State {
         match (self, other) {
             (State::NoData, State::NoData) => true,
             (State::Error(_), State::Error(_)) => false,
-            (State::Fetching, State::Fetching) => true,
+            (State::Downloading(a), State::Downloading(b)) => a == b,
+            (State::Decompressing, State::Decompressing) => true,
+            (State::Parsing, State::Parsing) => true,
             (State::Ready(_), State::Ready(_)) => false,
             _ => false,
         }
     }
 }
 
+impl State {
+    /// Whether a debug log is being downloaded or processed.
+    pub fn is_loading(&self) -> bool {
+        matches!(
+            self,
+            State::Downloading(_) | State::Decompressing | State::Parsing
+        )
+    }
+}
+
 impl Default for State {
     fn default() -> Self {
         Self::NoData
@@ -71,6 +154,37 @@ impl Default for State {
 pub struct SearchQuery {
     pub min_log_level: LogLevel,
     pub string: String,
+    pub regex: bool,
+    /// Tag that the displayed log entries must have, if any.
+    pub tag: Option<String>,
+    /// Pattern that the messages of the displayed log entries must have, if any.
+    pub message_pattern: Option<MessagePattern>,
+}
+
+/// Identifier that the displayed log entries were filtered by after clicking it in a message.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pivot {
+    pub identifier: String,
+    /// Active query from before filtering by the identifier.
+    pub previous_query: SearchQuery,
+}
+
+/// Results of a search query in a file.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SearchSummary {
+    /// Number of log entries that are displayed with the query.
+    pub entries: usize,
+    /// Most important level of those entries.
+    pub max_level: Option<LogLevel>,
+}
+
+impl SearchSummary {
+    pub fn merge(self, other: Self) -> Self {
+        Self {
+            entries: self.entries + other.entries,
+            max_level: self.max_level.max(other.max_level),
+        }
+    }
 }
 
 impl Default for SearchQuery {
@@ -78,6 +192,46 @@ impl Default for SearchQuery {
         Self {
             min_log_level: LogLevel::Error,
             string: Default::default(),
+            regex: false,
+            tag: None,
+            message_pattern: None,
+        }
+    }
+}
+
+impl SearchQuery {
+    /// Case-insensitive pattern for the query string, or `None` if there is nothing to search for.
+    pub fn pattern(&self) -> Result<Option<Regex>, regex::Error> {
+        if self.string.is_empty() {
+            return Ok(None);
+        }
+
+        let pattern = if self.regex {
+            self.string.clone()
+        } else {
+            regex::escape(&self.string)
+        };
+
+        RegexBuilder::new(&pattern)
+            .case_insensitive(true)
+            .build()
+            .map(Some)
+    }
+}
+
+/// Settings that affect how logs are displayed, but not which entries are.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DisplayOptions {
+    /// Minimum time between consecutive log entries for which a divider is shown, if any.
+    pub gap_threshold: Option<Duration>,
+    pub delta_columns: DeltaColumns,
+}
+
+impl Default for DisplayOptions {
+    fn default() -> Self {
+        Self {
+            gap_threshold: Some(Duration::minutes(5)),
+            delta_columns: Default::default(),
         }
     }
 }
@@ -86,6 +240,9 @@ impl Default for SearchQuery {
 pub enum Tab {
     Information,
     Logs,
+    Threads,
+    Jobs,
+    Patterns,
     Raw,
 }
 
@@ -100,37 +257,459 @@ impl Tab {
         match self {
             Tab::Information => classes!("fas", "fa-info"),
             Tab::Logs => classes!("fas", "fa-th-list"),
+            Tab::Threads => classes!("fas", "fa-stream"),
+            Tab::Jobs => classes!("fas", "fa-tasks"),
+            Tab::Patterns => classes!("fas", "fa-layer-group"),
             Tab::Raw => classes!("fas", "fa-file"),
         }
     }
+
+    /// Whether the tab is shown for files in the format, if it's known.
+    pub fn applicable_to(&self, format: Option<&dyn LogFormat>) -> bool {
+        match self {
+            Tab::Threads => format.is_some_and(|format| {
+                format
+                    .metadata_columns()
+                    .iter()
+                    .any(|column| column.role == Some(ColumnRole::Thread))
+            }),
+            Tab::Jobs => format.is_some_and(|format| format.has_jobs()),
+            Tab::Information | Tab::Logs | Tab::Patterns | Tab::Raw => true,
+        }
+    }
 }
 
-#[derive(Debug)]
-pub struct Model {
+/// A debug log that's open in its own tab, with its own tab, search query, file selection and
+/// state of the views of its files.
+#[derive(Debug, Default)]
+pub struct Document {
+    /// Identifies the document in messages about loading it, as its index can change.
+    pub id: usize,
     pub state: State,
-    pub debug_log_input: NodeRef,
-    pub debug_log_url: String,
     pub remote_object: Option<RemoteObject>,
     pub tab: Tab,
     pub pending_query: SearchQuery,
     pub active_query: SearchQuery,
+    /// Matches and highlights `active_query`, whose pattern it has compiled.
+    pub highlighter: Highlighter,
+    /// Timezone that timestamps are displayed, and thus searched, in.
+    pub timezone: DisplayTimezone,
+    /// Results of `active_query` in every file, if there are multiple.
+    pub search_summaries: BTreeMap<ArchiveFilename, SearchSummary>,
+    /// Results of `active_query` in all files.
+    pub search_summary: SearchSummary,
+    pub current_match: usize,
+    /// Index of the displayed log entry that's selected with the keyboard, if any.
+    pub cursor: Option<usize>,
+    /// Identifiers that the active query filters by, most recent last.
+    pub pivots: Vec<Pivot>,
+    /// Filter and collapsed sections of the information tab.
+    pub information: InformationOptions,
+    /// Lanes that are shown and pinned in the threads tab.
+    pub swimlanes: SwimlaneOptions,
+    /// Log entry that the "since anchor" deltas are relative to, if any.
+    pub anchor: Option<Anchor>,
+    /// Log entries shown in the detail drawer, if any.
+    pub selection: Option<EntrySelection>,
+    /// Aborts the download in progress, if there is one.
+    pub abort_controller: Option<AbortController>,
+}
+
+impl Document {
+    fn new(id: usize) -> Self {
+        Self {
+            id,
+            ..Default::default()
+        }
+    }
+
+    /// Short description of the debug log for its tab.
+    pub fn label(&self) -> String {
+        match &self.remote_object {
+            Some(remote_object) => {
+                let key = &remote_object.key()[..8];
+
+                match remote_object.version() {
+                    Some(version) => format!("{} {version} ({key})", remote_object.platform()),
+                    None => format!("{} ({key})", remote_object.platform()),
+                }
+            }
+            None => "New".to_owned(),
+        }
+    }
+
+    /// Loads the debug log from the cache if it's there, and fetches it otherwise.
+    fn fetch(&mut self, ctx: &yew::prelude::Context<Model>, fetch_mode: &FetchMode) {
+        let id = self.id;
+        let remote_object = self.remote_object.as_ref().unwrap();
+        let key = remote_object.key().to_owned();
+
+        self.abort_controller = AbortController::new().ok();
+        let signal = self.abort_controller.as_ref().map(AbortController::signal);
+        let request = reqwasm::http::Request::get(&remote_object.fetchable_url(fetch_mode))
+            .abort_signal(signal.as_ref());
+
+        let link = ctx.link().clone();
+
+        ctx.link().send_future(async move {
+            if let Ok(Some(payload)) = cache::load(&key).await {
+                return Msg::FinishedLoadFromCache(id, payload);
+            }
+
+            match request.send().await {
+                Ok(response) => match response.status() {
+                    200 => match download::read_body(&response, |progress| {
+                        link.send_message(Msg::DownloadProgress(id, progress))
+                    })
+                    .await
+                    {
+                        Ok(payload) => Msg::FinishedDownload(id, payload),
+                        Err(e) => {
+                            Msg::FetchError(id, e.context("fetching debug log was interrupted"))
+                        }
+                    },
+                    _ => Msg::FetchError(
+                        id,
+                        anyhow!("status code {}", response.status())
+                            .context("fetching debug log finished unsuccessfully"),
+                    ),
+                },
+                Err(e) => Msg::FetchError(
+                    id,
+                    anyhow::Error::from(e).context("couldn't start fetching debug log"),
+                ),
+            }
+        })
+    }
+
+    /// Aborts the download in progress, if there is one.
+    fn cancel(&mut self) {
+        if let Some(abort_controller) = self.abort_controller.take() {
+            abort_controller.abort();
+        }
+    }
+
+    /// Starts processing the downloaded payload, which is cached once it's known to be valid
+    /// if `cache` is `true`.
+    fn process(
+        &mut self,
+        ctx: &yew::prelude::Context<Model>,
+        payload: Vec<u8>,
+        cache: bool,
+    ) -> bool {
+        self.abort_controller = None;
+
+        match self.remote_object.as_ref().unwrap().platform() {
+            Platform::Ios => {
+                Model::after_render(ctx, Msg::Decompress(self.id, payload, cache));
+                self.state.neq_assign(State::Decompressing)
+            }
+            Platform::Android | Platform::Desktop => {
+                Model::after_render(ctx, Msg::ParseText(self.id, payload, cache));
+                self.state.neq_assign(State::Parsing)
+            }
+        }
+    }
+
+    fn save_to_cache(&self, ctx: &yew::prelude::Context<Model>, payload: Vec<u8>) {
+        let remote_object = self.remote_object.clone().unwrap();
+        Model::update_cache(
+            ctx,
+            async move { cache::save(&remote_object, &payload).await },
+        );
+    }
+
+    fn load_archive(&mut self, entries: Vec<(ArchiveFilename, Vec<u8>)>) {
+        let files = entries
+            .into_iter()
+            .map(|(name, bytes)| {
+                let file = File::from_bytes(
+                    self.remote_object.clone().unwrap(),
+                    Some(name.clone()),
+                    bytes,
+                );
+                (name, file)
+            })
+            .collect::<BTreeMap<_, _>>();
+
+        let last_for_app_id = |app_id: AppId| {
+            files
+                .keys()
+                .rev()
+                .find(|name| matches!(name, ArchiveFilename::Log(name) if name.app_id == app_id))
+        };
+        let active_filename = last_for_app_id(AppId::Signal)
+            .or_else(|| last_for_app_id(AppId::NotificationServiceExtension))
+            .or_else(|| last_for_app_id(AppId::ShareAppExtension))
+            .unwrap_or_else(|| files.keys().next().unwrap())
+            .clone();
+
+        self.state = State::Ready(Object::Multiple {
+            files,
+            active_filename,
+        });
+        self.update_search_summaries();
+    }
+
+    /// Number of search query matches in the displayed entries of the active file's logs.
+    pub fn match_count(&self) -> usize {
+        if !self.state.is_ready() {
+            return 0;
+        }
+
+        self.active_file()
+            .count_matches(&self.active_query, &self.highlighter, self.timezone)
+    }
+
+    fn update_search_summaries(&mut self) {
+        self.search_summaries = match &self.state {
+            State::Ready(Object::Multiple { files, .. }) => files
+                .iter()
+                .map(|(name, file)| {
+                    (
+                        name.clone(),
+                        file.search_summary(&self.active_query, &self.highlighter, self.timezone),
+                    )
+                })
+                .collect(),
+            _ => Default::default(),
+        };
+
+        self.search_summary = match &self.state {
+            State::Ready(Object::Single(file)) => {
+                file.search_summary(&self.active_query, &self.highlighter, self.timezone)
+            }
+            _ => self
+                .search_summaries
+                .values()
+                .fold(Default::default(), |total, summary| total.merge(*summary)),
+        };
+    }
+
+    /// Levels of the log entries displayed in the active file's logs, in the order they're in.
+    pub fn displayed_levels(&self) -> Vec<LogLevel> {
+        if !self.state.is_ready() {
+            return vec![];
+        }
+
+        self.active_file()
+            .displayed_levels(&self.active_query, &self.highlighter, self.timezone)
+    }
+
+    /// Makes the query the active one, returning whether it changed.
+    fn apply_query(&mut self, query: SearchQuery) -> bool {
+        self.current_match = 0;
+        self.cursor = None;
+        self.pivots.clear();
+        self.pending_query = query.clone();
+
+        if self.active_query.neq_assign(query) {
+            self.highlighter = Highlighter::new(&self.active_query);
+            self.update_search_summaries();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Query for entries of any level that contain the string.
+    fn filter_query(&self, string: String, regex: bool, tag: Option<String>) -> SearchQuery {
+        let format = self.format();
+
+        SearchQuery {
+            min_log_level: LogLevel::iter()
+                .find(|level| level.applicable_to(format))
+                .unwrap(),
+            string,
+            regex,
+            tag,
+            message_pattern: None,
+        }
+    }
+
+    /// Displays only the log entries in any file that mention the identifier.
+    fn pivot(&mut self, identifier: String) -> bool {
+        if !self.state.is_ready()
+            || matches!(self.pivots.last(), Some(pivot) if pivot.identifier == identifier)
+        {
+            return false;
+        }
+
+        let mut pivots = std::mem::take(&mut self.pivots);
+        pivots.push(Pivot {
+            identifier: identifier.clone(),
+            previous_query: self.active_query.clone(),
+        });
+
+        self.apply_query(self.filter_query(identifier, false, None));
+        self.pivots = pivots;
+        self.tab = Tab::Logs;
+
+        true
+    }
+
+    /// Restores the query from before the pivot at the index, dropping it and the ones after it.
+    fn pop_pivot(&mut self, index: usize) -> bool {
+        let Some(previous_query) = self
+            .pivots
+            .get(index)
+            .map(|pivot| pivot.previous_query.clone())
+        else {
+            return false;
+        };
+
+        let mut pivots = std::mem::take(&mut self.pivots);
+        pivots.truncate(index);
+        self.apply_query(previous_query);
+        self.pivots = pivots;
+
+        true
+    }
+
+    /// Format of the active file, if it's been parsed.
+    pub fn format(&self) -> Option<&'static dyn LogFormat> {
+        match &self.state {
+            State::Ready(_) => self.active_file().format(),
+            _ => None,
+        }
+    }
+
+    pub fn active_file(&self) -> &File {
+        match &self.state {
+            State::Ready(Object::Single(file)) => file,
+            State::Ready(Object::Multiple {
+                files,
+                active_filename,
+            }) => files.get(active_filename).unwrap(),
+            _ => panic!("State is not `Ready`"),
+        }
+    }
+
+    /// Handles a message about loading the debug log.
+    fn update_loading(
+        &mut self,
+        ctx: &yew::prelude::Context<Model>,
+        msg: Msg,
+    ) -> anyhow::Result<bool> {
+        match msg {
+            Msg::FetchError(_, e) => {
+                // Errors of cancelled downloads are expected.
+                if self.state.is_downloading() {
+                    self.abort_controller = None;
+                    Err(e)
+                } else {
+                    Ok(false)
+                }
+            }
+            Msg::DownloadProgress(_, progress) => {
+                Ok(self.state.is_downloading()
+                    && self.state.neq_assign(State::Downloading(progress)))
+            }
+            Msg::FinishedDownload(_, payload) => {
+                Ok(self.state.is_downloading() && self.process(ctx, payload, true))
+            }
+            Msg::FinishedLoadFromCache(_, payload) => {
+                Ok(self.state.is_downloading() && self.process(ctx, payload, false))
+            }
+            Msg::Decompress(_, payload, cache) => {
+                if !self.state.is_decompressing() {
+                    return Ok(false);
+                }
+
+                let entries = Model::decompress(&payload)?;
+
+                if cache {
+                    self.save_to_cache(ctx, payload);
+                }
+
+                Model::after_render(ctx, Msg::ParseArchive(self.id, entries));
+                Ok(self.state.neq_assign(State::Parsing))
+            }
+            Msg::ParseText(_, payload, cache) => {
+                if !self.state.is_parsing() {
+                    return Ok(false);
+                }
+
+                if cache {
+                    self.save_to_cache(ctx, payload.clone());
+                }
+
+                let file = File::from_bytes(self.remote_object.clone().unwrap(), None, payload);
+                self.state = State::Ready(Object::Single(file));
+                self.update_search_summaries();
+                Ok(true)
+            }
+            Msg::ParseArchive(_, entries) => {
+                if !self.state.is_parsing() {
+                    return Ok(false);
+                }
+
+                self.load_archive(entries);
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Model {
+    /// Open debug logs, of which there is always at least one.
+    pub documents: Vec<Document>,
+    /// Index of the document that's displayed.
+    pub active_document: usize,
+    next_document_id: usize,
+    pub debug_log_input: NodeRef,
+    pub debug_log_url: String,
+    /// Debug logs mentioned in the text entered instead of a URL, if any.
+    pub found_remote_objects: Vec<RemoteObject>,
+    /// Why the entered URL couldn't be opened, if it couldn't.
+    pub url_error: Option<anyhow::Error>,
+    pub scroll_to_match: bool,
+    pub scroll_to_cursor: bool,
+    pub search_input: NodeRef,
+    pub focus_search: bool,
+    pub show_shortcuts: bool,
+    pub display_options: DisplayOptions,
     pub ui_expanded: bool,
+    /// Logs whose payloads are cached, most recently fetched first.
+    pub recent_logs: Vec<RecentLog>,
+    pub fetch_mode: FetchMode,
+    pub health_check: HealthCheck,
+    /// Listens for keyboard shortcuts for as long as it's kept.
+    _keydown_listener: Option<EventListener>,
 }
 
 impl Component for Model {
     type Message = Msg;
     type Properties = ();
 
-    fn create(_ctx: &yew::prelude::Context<Self>) -> Self {
+    fn create(ctx: &yew::prelude::Context<Self>) -> Self {
+        Self::update_cache(ctx, async { Ok(()) });
+
+        if settings::initial_log_url().is_some() {
+            ctx.link().send_message(Msg::Start);
+        }
+
         Self {
-            state: Default::default(),
+            documents: vec![Document::new(0)],
+            active_document: 0,
+            next_document_id: 1,
             debug_log_input: NodeRef::default(),
-            debug_log_url: Default::default(),
-            remote_object: None,
-            tab: Default::default(),
-            pending_query: Default::default(),
-            active_query: Default::default(),
+            debug_log_url: settings::initial_log_url().unwrap_or_default(),
+            found_remote_objects: vec![],
+            url_error: None,
+            scroll_to_match: false,
+            scroll_to_cursor: false,
+            search_input: NodeRef::default(),
+            focus_search: false,
+            show_shortcuts: false,
+            display_options: Default::default(),
             ui_expanded: false,
+            recent_logs: vec![],
+            fetch_mode: settings::initial_fetch_mode(),
+            health_check: Default::default(),
+            _keydown_listener: keyboard::listen(ctx.link().clone()),
         }
     }
 
@@ -140,12 +719,44 @@ impl Component for Model {
                 let _ = input.focus();
             }
         }
+
+        if self.scroll_to_match {
+            self.scroll_to_match = false;
+
+            if let Some(element) =
+                Self::element_by_id(&Highlighter::match_id(self.document().current_match))
+            {
+                element.scroll_into_view();
+            }
+        }
+
+        if self.scroll_to_cursor {
+            self.scroll_to_cursor = false;
+
+            if let Some(element) = self
+                .document()
+                .cursor
+                .and_then(|cursor| Self::element_by_id(&Highlighter::row_id(cursor)))
+            {
+                element.scroll_into_view_with_scroll_into_view_options(
+                    ScrollIntoViewOptions::new().block(ScrollLogicalPosition::Center),
+                );
+            }
+        }
+
+        if self.focus_search {
+            self.focus_search = false;
+
+            if let Some(input) = self.search_input.cast::<HtmlInputElement>() {
+                let _ = input.focus();
+            }
+        }
     }
 
     fn update(&mut self, ctx: &yew::prelude::Context<Self>, msg: Self::Message) -> bool {
         match self.update_inner(ctx, msg) {
             Ok(should_render) => should_render,
-            Err(e) => self.state.neq_assign(State::Error(e)),
+            Err(e) => self.document_mut().state.neq_assign(State::Error(e)),
         }
     }
 
@@ -155,41 +766,167 @@ impl Component for Model {
 }
 
 impl Model {
-    fn fetch(&self, ctx: &yew::prelude::Context<Self>, url: &str, binary: bool) {
-        let request = reqwasm::http::Request::get(url);
+    pub(super) fn document(&self) -> &Document {
+        &self.documents[self.active_document]
+    }
 
-        ctx.link().send_future(async move {
-            match request.send().await {
-                Ok(response) => match response.status() {
-                    200 => {
-                        if binary {
-                            let bytes = response.binary().await.unwrap();
-                            Msg::FinishedFetchBinary(bytes)
-                        } else {
-                            let text = response.text().await.unwrap();
-                            Msg::FinishedFetchText(text)
-                        }
-                    }
-                    _ => Msg::FetchError(
-                        anyhow!("status code {}", response.status())
-                            .context("fetching debug log finished unsuccessfully"),
-                    ),
-                },
-                Err(e) => Msg::FetchError(
-                    anyhow::Error::from(e).context("couldn't start fetching debug log"),
-                ),
+    fn document_mut(&mut self) -> &mut Document {
+        &mut self.documents[self.active_document]
+    }
+
+    fn element_by_id(id: &str) -> Option<web_sys::Element> {
+        web_sys::window()
+            .and_then(|window| window.document())
+            .and_then(|document| document.get_element_by_id(id))
+    }
+
+    /// Moves the row cursor of the displayed document, returning whether it moved.
+    fn update_cursor(&mut self, cursor: Option<usize>) -> bool {
+        if cursor.is_some() && self.document_mut().cursor.neq_assign(cursor) {
+            self.scroll_to_cursor = true;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn handle_shortcut(
+        &mut self,
+        ctx: &yew::prelude::Context<Self>,
+        shortcut: Shortcut,
+    ) -> anyhow::Result<bool> {
+        match shortcut {
+            Shortcut::ToggleHelp => {
+                self.show_shortcuts = !self.show_shortcuts;
+                return Ok(true);
             }
-        })
+            Shortcut::HideHelp => {
+                let hid_details = self.document_mut().selection.take().is_some();
+                return Ok(self.show_shortcuts.neq_assign(false) || hid_details);
+            }
+            _ => {}
+        }
+
+        let document = self.document();
+
+        if !document.state.is_ready() {
+            return Ok(false);
+        }
+
+        match shortcut {
+            Shortcut::FocusSearch => {
+                // The search input is only shown on some tabs.
+                if !matches!(document.tab, Tab::Logs | Tab::Threads) {
+                    self.document_mut().tab = Tab::Logs;
+                }
+
+                self.focus_search = true;
+                Ok(true)
+            }
+            Shortcut::NextRow | Shortcut::PreviousRow if document.tab.is_logs() => {
+                let count = document.displayed_levels().len();
+                let cursor =
+                    keyboard::move_cursor(document.cursor, count, shortcut == Shortcut::NextRow);
+
+                Ok(self.update_cursor(cursor))
+            }
+            Shortcut::NextProblem | Shortcut::PreviousProblem if document.tab.is_logs() => {
+                let cursor = keyboard::find_problem(
+                    &document.displayed_levels(),
+                    document.cursor,
+                    shortcut == Shortcut::NextProblem,
+                );
+
+                Ok(self.update_cursor(cursor))
+            }
+            Shortcut::NextMatch => self.update_inner(ctx, Msg::NextMatch),
+            Shortcut::PreviousMatch => self.update_inner(ctx, Msg::PreviousMatch),
+            Shortcut::Tab(index) => {
+                let format = document.format();
+
+                match Tab::iter()
+                    .filter(|tab| tab.applicable_to(format))
+                    .nth(index)
+                {
+                    Some(tab) => self.update_inner(ctx, Msg::UpdateTab(tab)),
+                    None => Ok(false),
+                }
+            }
+            _ => Ok(false),
+        }
     }
 
-    pub(super) fn active_file(&self) -> &File {
-        match &self.state {
-            State::Ready(Object::Single(file)) => file,
-            State::Ready(Object::Multiple {
-                files,
-                active_filename,
-            }) => files.get(active_filename).unwrap(),
-            _ => panic!("State is not `Ready`"),
+    fn new_document(&mut self) -> Document {
+        let document = Document::new(self.next_document_id);
+        self.next_document_id += 1;
+        document
+    }
+
+    /// Displays the document, with the URL of its debug log in the input.
+    fn activate_document(&mut self, index: usize) {
+        self.active_document = index;
+        self.debug_log_url = self
+            .document()
+            .remote_object
+            .as_ref()
+            .map(RemoteObject::debuglogs_url)
+            .unwrap_or_default();
+    }
+
+    /// Sends the message once the current state has been rendered.
+    fn after_render(ctx: &yew::prelude::Context<Self>, msg: Msg) {
+        ctx.link().send_future(async move {
+            download::yield_to_browser().await;
+            msg
+        });
+    }
+
+    /// Runs the operation on the cache, and then updates the list of recent logs.
+    ///
+    /// The cache is only a convenience, so failing to update it is ignored.
+    fn update_cache(
+        ctx: &yew::prelude::Context<Self>,
+        operation: impl Future<Output = anyhow::Result<()>> + 'static,
+    ) {
+        ctx.link().send_future(async move {
+            let _ = operation.await;
+            Msg::UpdateRecentLogs(cache::recent_logs().await.unwrap_or_default())
+        });
+    }
+
+    fn decompress(bytes: &[u8]) -> anyhow::Result<Vec<(ArchiveFilename, Vec<u8>)>> {
+        let mut zip = ZipArchive::new(Cursor::new(bytes))
+            .context("couldn't read the debug log file as a `zip`")?;
+
+        let mut files = vec![];
+
+        for i in 0..zip.len() {
+            let mut file = zip.by_index(i)?;
+
+            let name = match file.name().parse::<LogFilename>() {
+                Ok(name) => ArchiveFilename::Log(Rc::new(name)),
+                Err(_) => ArchiveFilename::Other(file.name().to_owned()),
+            };
+
+            let mut bytes: Vec<u8> = vec![];
+            io::copy(&mut file, &mut bytes).context("couldn't copy a log file into a `Vec<u8>`")?;
+
+            files.push((name, bytes));
+        }
+
+        ensure!(!files.is_empty(), "no files in zip"); // TODO: maybe should just be a notice instead of an error
+
+        Ok(files)
+    }
+
+    /// Passes a message about loading a document on to it, unless it has been closed since.
+    fn update_loading(&mut self, ctx: &yew::prelude::Context<Self>, id: usize, msg: Msg) -> bool {
+        match self.documents.iter_mut().find(|document| document.id == id) {
+            Some(document) => match document.update_loading(ctx, msg) {
+                Ok(should_render) => should_render,
+                Err(e) => document.state.neq_assign(State::Error(e)),
+            },
+            None => false,
         }
     }
 
@@ -199,105 +936,352 @@ impl Model {
         msg: <Self as Component>::Message,
     ) -> anyhow::Result<bool> {
         match msg {
-            Msg::UpdateUrl(value) => Ok(self.debug_log_url.neq_assign(value)),
-            Msg::Start => match &self.state {
-                State::NoData | State::Error(_) | State::Ready(_) => {
-                    if let Some(input) = self.debug_log_input.cast::<HtmlInputElement>() {
-                        let _ = input.blur();
+            Msg::UpdateUrl(value) => {
+                self.found_remote_objects = remote_object::find_remote_objects(&value);
+                let hid_error = self.url_error.take().is_some();
+                Ok(self.debug_log_url.neq_assign(value) || hid_error)
+            }
+            Msg::DismissFoundRemoteObjects => Ok(self.found_remote_objects.neq_assign(vec![])),
+            Msg::OpenFoundRemoteObjects => {
+                for remote_object in self.found_remote_objects.clone() {
+                    self.debug_log_url = remote_object.debuglogs_url();
+                    self.update_inner(ctx, Msg::Start)?;
+                }
+
+                Ok(true)
+            }
+            Msg::Start => {
+                if let Some(input) = self.debug_log_input.cast::<HtmlInputElement>() {
+                    let _ = input.blur();
+                }
+
+                // Pasted text that isn't just a URL is searched for one.
+                let reference = match self
+                    .debug_log_url
+                    .trim()
+                    .to_lowercase()
+                    .parse::<RemoteObject>()
+                {
+                    Ok(reference) => Ok(reference),
+                    Err(e) => remote_object::find_remote_objects(&self.debug_log_url)
+                        .into_iter()
+                        .next()
+                        .ok_or(e),
+                };
+
+                // The displayed document is left as it is, as it may have a log open.
+                let reference = match reference.context("failed to parse the debug log URL") {
+                    Ok(reference) => reference,
+                    Err(e) => {
+                        self.url_error = Some(e);
+                        return Ok(true);
                     }
+                };
+                self.url_error = None;
 
-                    self.tab = Default::default();
-                    self.pending_query = Default::default();
-                    self.active_query = Default::default();
+                // A log that's already open is switched to instead of being opened again.
+                if let Some(index) = self.documents.iter().position(|document| {
+                    matches!(&document.remote_object, Some(remote_object) if remote_object.key() == reference.key())
+                        && (document.state.is_ready() || document.state.is_loading())
+                }) {
+                    self.activate_document(index);
+                    return Ok(true);
+                }
 
-                    let reference = self
-                        .debug_log_url
-                        .trim()
-                        .to_lowercase()
-                        .parse::<RemoteObject>()
-                        .context("failed to parse the debug log URL")?;
+                // A log that's displayed or still loading stays open in its own tab.
+                let document = self.new_document();
+                if self.document().state.is_ready() || self.document().state.is_loading() {
+                    self.documents.push(document);
+                    self.activate_document(self.documents.len() - 1);
+                } else {
+                    *self.document_mut() = document;
+                }
+
+                self.debug_log_url = reference.debuglogs_url();
+
+                let document = &mut self.documents[self.active_document];
+                document.remote_object = Some(reference);
+                document.fetch(ctx, &self.fetch_mode);
+                document.state = State::Downloading(Default::default());
+
+                Ok(true)
+            }
+            Msg::FetchError(id, _)
+            | Msg::DownloadProgress(id, _)
+            | Msg::FinishedDownload(id, _)
+            | Msg::FinishedLoadFromCache(id, _)
+            | Msg::Decompress(id, _, _)
+            | Msg::ParseText(id, _, _)
+            | Msg::ParseArchive(id, _) => Ok(self.update_loading(ctx, id, msg)),
+            Msg::CancelFetch => {
+                let document = self.document_mut();
+                document.cancel();
 
-                    self.fetch(
-                        ctx,
-                        &reference.fetchable_url(),
-                        matches!(reference.platform(), Platform::Ios),
-                    );
+                Ok(document.state.is_loading() && document.state.neq_assign(State::NoData))
+            }
+            Msg::UpdateRecentLogs(logs) => Ok(self.recent_logs.neq_assign(logs)),
+            Msg::OpenUrl(url) => {
+                self.debug_log_url = url;
+                self.update_inner(ctx, Msg::Start)
+            }
+            Msg::UpdateRecentLogLabel(key, label) => {
+                Self::update_cache(ctx, async move { cache::update_label(&key, label).await });
+                Ok(false)
+            }
+            Msg::RemoveRecentLog(key) => {
+                Self::update_cache(ctx, async move { cache::remove(&key).await });
+                Ok(false)
+            }
+            Msg::ClearCache => {
+                Self::update_cache(ctx, cache::clear());
+                Ok(false)
+            }
+            Msg::UpdateFetchMode(mode) => {
+                settings::save_fetch_mode(&mode);
+                self.health_check = HealthCheck::NotChecked;
+                Ok(self.fetch_mode.neq_assign(mode))
+            }
+            Msg::CheckHealth => {
+                let request = reqwasm::http::Request::get(&self.fetch_mode.health_check_url());
 
-                    self.debug_log_url = reference.debuglogs_url();
-                    self.remote_object = Some(reference);
+                ctx.link().send_future(async move {
+                    Msg::FinishedHealthCheck(match request.send().await {
+                        Ok(response) if response.ok() => HealthCheck::Ok,
+                        Ok(response) => {
+                            HealthCheck::Failed(format!("status code {}", response.status()))
+                        }
+                        Err(e) => HealthCheck::Failed(e.to_string()),
+                    })
+                });
 
-                    Ok(self.state.neq_assign(State::Fetching))
+                Ok(self.health_check.neq_assign(HealthCheck::Checking))
+            }
+            Msg::FinishedHealthCheck(health_check) => {
+                Ok(self.health_check.neq_assign(health_check))
+            }
+            Msg::SelectDocument(index) => {
+                if index >= self.documents.len() || index == self.active_document {
+                    return Ok(false);
                 }
-                _ => Ok(false),
-            },
-            Msg::FetchError(e) => Err(e),
-            Msg::FinishedFetchText(text) => {
-                let file = File::from_text(self.remote_object.clone().unwrap(), None, text);
 
-                Ok(self.state.neq_assign(State::Ready(Object::Single(file))))
+                self.activate_document(index);
+                Ok(true)
             }
-            Msg::FinishedFetchBinary(bytes) => {
-                let mut zip = ZipArchive::new(Cursor::new(bytes.as_slice()))
-                    .context("couldn't read the debug log file as a `zip`")?;
+            Msg::CloseDocument(index) => {
+                if index >= self.documents.len() {
+                    return Ok(false);
+                }
 
-                let mut files = BTreeMap::new();
+                self.documents.remove(index).cancel();
 
-                for i in 0..zip.len() {
-                    let mut file = zip.by_index(i)?;
+                if self.documents.is_empty() {
+                    let document = self.new_document();
+                    self.documents.push(document);
+                }
 
-                    let name = Rc::new(
-                        file.name()
-                            .parse::<LogFilename>()
-                            .context("couldn't parse a file's name")?,
-                    );
+                let active_document = if self.active_document > index {
+                    self.active_document - 1
+                } else {
+                    self.active_document.min(self.documents.len() - 1)
+                };
+                self.activate_document(active_document);
 
-                    let mut bytes: Vec<u8> = vec![];
-                    io::copy(&mut file, &mut bytes)
-                        .context("couldn't copy a log file into a `Vec<u8>`")?;
-                    let text = String::from_utf8(bytes)
-                        .context("couldn't turn a `Vec<u8>` into a `String`")?;
+                Ok(true)
+            }
+            Msg::NewDocument => {
+                let document = self.new_document();
+                self.documents.push(document);
+                self.activate_document(self.documents.len() - 1);
 
-                    files.insert(
-                        Rc::clone(&name),
-                        File::from_text(self.remote_object.clone().unwrap(), Some(name), text),
-                    );
+                if let Some(input) = self.debug_log_input.cast::<HtmlInputElement>() {
+                    let _ = input.focus();
                 }
 
-                ensure!(!files.is_empty(), "no files in zip"); // TODO: maybe should just be a notice instead of an error
+                Ok(true)
+            }
+            Msg::SearchAllDocuments => {
+                let query = self.document().pending_query.clone();
+
+                if query.pattern().is_err() {
+                    return Ok(false);
+                }
 
-                let last_for_app_id = |app_id| files.keys().filter(|k| k.app_id == app_id).last();
-                let active_filename =
-                    Rc::clone(last_for_app_id(AppId::Signal).unwrap_or_else(|| {
-                        last_for_app_id(AppId::NotificationServiceExtension)
-                            .unwrap_or_else(|| last_for_app_id(AppId::ShareAppExtension).unwrap())
-                    }));
+                for document in &mut self.documents {
+                    if document.state.is_ready() {
+                        document.apply_query(query.clone());
+                    }
+                }
 
-                Ok(self.state.neq_assign(State::Ready(Object::Multiple {
-                    files,
-                    active_filename,
-                })))
+                Ok(true)
             }
-            Msg::UpdateActiveFile(filename) => Ok(
-                if let State::Ready(Object::Multiple {
-                    active_filename, ..
-                }) = &mut self.state
-                {
-                    active_filename.neq_assign(filename)
-                } else {
-                    false
-                },
-            ),
-            Msg::UpdateTab(tab) => Ok(self.tab.neq_assign(tab)),
+            Msg::UpdateActiveFile(filename) => {
+                let document = &mut self.documents[self.active_document];
+
+                Ok(
+                    if let State::Ready(Object::Multiple {
+                        active_filename, ..
+                    }) = &mut document.state
+                    {
+                        document.current_match = 0;
+                        document.cursor = None;
+                        document.anchor = None;
+                        document.selection = None;
+
+                        // Other files can't be parsed, so only their raw text is useful.
+                        if matches!(filename, ArchiveFilename::Other(_)) {
+                            document.tab = Tab::Raw;
+                        } else if !document.active_query.string.is_empty()
+                            && matches!(document.search_summaries.get(&filename), Some(summary) if summary.entries > 0)
+                        {
+                            document.tab = Tab::Logs;
+                            self.scroll_to_match = true;
+                        }
+
+                        active_filename.neq_assign(filename)
+                    } else {
+                        false
+                    },
+                )
+            }
+            Msg::UpdateTab(tab) => Ok(self.document_mut().tab.neq_assign(tab)),
             Msg::UpdateMinLogLevel(value) => Ok(self
+                .document_mut()
                 .pending_query
                 .min_log_level
                 .neq_assign(value.parse().unwrap())),
-            Msg::UpdateQuery(value) => Ok(self.pending_query.string.neq_assign(value)),
+            Msg::UpdateQuery(value) => {
+                Ok(self.document_mut().pending_query.string.neq_assign(value))
+            }
+            Msg::UpdateQueryRegex => {
+                let pending_query = &mut self.document_mut().pending_query;
+                pending_query.regex = !pending_query.regex;
+                Ok(true)
+            }
             Msg::UpdateUiExpanded => {
                 self.ui_expanded = !self.ui_expanded;
                 Ok(true)
             }
-            Msg::ApplySearchQuery => Ok(self.active_query.neq_assign(self.pending_query.clone())),
+            Msg::ApplySearchQuery => {
+                let document = self.document_mut();
+
+                if document.pending_query.pattern().is_err() {
+                    return Ok(false);
+                }
+
+                let query = document.pending_query.clone();
+                Ok(document.apply_query(query))
+            }
+            Msg::PreviousMatch | Msg::NextMatch => {
+                let document = &mut self.documents[self.active_document];
+                let match_count = document.match_count();
+
+                if match_count == 0 {
+                    return Ok(false);
+                }
+
+                document.current_match = if matches!(msg, Msg::NextMatch) {
+                    (document.current_match + 1) % match_count
+                } else {
+                    (document.current_match + match_count - 1) % match_count
+                };
+                self.scroll_to_match = true;
+
+                Ok(true)
+            }
+            Msg::FilterByRegex(regex) => {
+                let document = self.document_mut();
+
+                document.apply_query(document.filter_query(regex, true, None));
+                document.tab = Tab::Logs;
+
+                Ok(true)
+            }
+            Msg::FilterByPattern(template, tag) => {
+                let document = self.document_mut();
+                let query = SearchQuery {
+                    message_pattern: Some(MessagePattern::new(template)),
+                    ..document.filter_query(String::new(), false, tag)
+                };
+
+                document.apply_query(query);
+                document.tab = Tab::Logs;
+
+                Ok(true)
+            }
+            Msg::ClearQueryTag => {
+                let document = self.document_mut();
+                let query = SearchQuery {
+                    tag: None,
+                    ..document.active_query.clone()
+                };
+
+                Ok(document.apply_query(query))
+            }
+            Msg::ClearQueryPattern => {
+                let document = self.document_mut();
+                let query = SearchQuery {
+                    message_pattern: None,
+                    ..document.active_query.clone()
+                };
+
+                Ok(document.apply_query(query))
+            }
+            Msg::UpdateGapThreshold(value) => Ok(self
+                .display_options
+                .gap_threshold
+                .neq_assign(value.parse().ok().map(Duration::minutes))),
+            Msg::ShowLane(thread_id) => {
+                self.document_mut().swimlanes.show(thread_id);
+                Ok(true)
+            }
+            Msg::HideLane(thread_id) => {
+                self.document_mut().swimlanes.hide(thread_id);
+                Ok(true)
+            }
+            Msg::UpdateInformationFilter(filter) => {
+                Ok(self.document_mut().information.filter.neq_assign(filter))
+            }
+            Msg::ToggleInformationSection(path) => {
+                self.document_mut().information.toggle_collapsed(path);
+                Ok(true)
+            }
+            Msg::UpdateInformationCollapsed(collapsed) => Ok(self
+                .document_mut()
+                .information
+                .collapsed
+                .neq_assign(collapsed)),
+            Msg::UpdateTimezone(value) => {
+                let document = self.document_mut();
+
+                if !document.timezone.neq_assign(value.parse().unwrap()) {
+                    return Ok(false);
+                }
+
+                // Matches are searched for in the timestamps as they're displayed.
+                document.current_match = 0;
+                document.update_search_summaries();
+                Ok(true)
+            }
+            Msg::UpdateDeltaColumns(value) => Ok(self
+                .display_options
+                .delta_columns
+                .neq_assign(value.parse().unwrap())),
+            Msg::UpdateAnchor(anchor) => Ok(self.document_mut().anchor.neq_assign(anchor)),
+            Msg::ToggleLanePinned(thread_id) => {
+                self.document_mut().swimlanes.toggle_pinned(thread_id);
+                Ok(true)
+            }
+            Msg::SelectEntry(section, number, extend) => {
+                let document = self.document_mut();
+                let selection =
+                    EntrySelection::select(document.selection.as_ref(), &section, number, extend);
+
+                Ok(document.selection.neq_assign(Some(selection)))
+            }
+            Msg::CloseEntryDetails => Ok(self.document_mut().selection.take().is_some()),
+            Msg::Pivot(identifier) => Ok(self.document_mut().pivot(identifier)),
+            Msg::PopPivot(index) => Ok(self.document_mut().pop_pivot(index)),
+            Msg::Shortcut(shortcut) => self.handle_shortcut(ctx, shortcut),
         }
     }
 }
@@ -1,15 +1,17 @@
+use std::rc::Rc;
+
 use chrono::Duration;
 use nom::{
     branch::alt,
-    bytes::complete::{is_a, is_not, tag, take_till, take_until, take_while},
+    bytes::complete::{is_not, tag, take_till, take_until, take_while},
     character::complete::{self, digit1, multispace0, newline, space0, space1},
     combinator::{map, map_opt, not, opt, peek, success, value, verify},
     multi::{count, many0, many1, separated_list1},
-    sequence::{delimited, pair, preceded, separated_pair, tuple},
+    sequence::{delimited, pair, preceded, separated_pair, terminated, tuple},
     IResult,
 };
 
-use crate::{parsers::*, post_processing, remote_object};
+use crate::{parsers::*, post_processing, remote_object, LogLevel, Platform};
 
 const LOGCAT_SECTION_NAME: &str = "LOGCAT";
 const LOGGER_SECTION_NAME: &str = "LOGGER";
@@ -120,6 +122,31 @@ impl IndentedSectionType {
     }
 }
 
+/// A subsection of a base section that's introduced by a line of its own and lists its indented
+/// subsections, or says that there are none.
+struct ListedSubsection {
+    raw_name: &'static str,
+    name: &'static str,
+    explicit_none: &'static str,
+    ty: IndentedSectionType,
+}
+
+const PROFILES: ListedSubsection = ListedSubsection {
+    raw_name: "Profiles:",
+    name: "Profiles",
+    explicit_none: "No notification profiles",
+    ty: NotificationProfiles,
+};
+
+const OWNERSHIP_INFO: ListedSubsection = ListedSubsection {
+    raw_name: "Ownership Info:",
+    name: "Ownership Info",
+    explicit_none: "No ownership info to display.",
+    ty: OwnershipInfo,
+};
+
+const LISTED_SUBSECTIONS: [ListedSubsection; 2] = [PROFILES, OWNERSHIP_INFO];
+
 #[traceable_configurable_parser]
 fn indented_subsection(
     ty: IndentedSectionType,
@@ -241,11 +268,23 @@ fn info_section<'a>(depth: SectionLevel) -> impl FnMut(Span) -> IResult<Span, Se
         |name: &str| name != LOGCAT_SECTION_NAME && name != LOGGER_SECTION_NAME,
     )(input)?;
 
-    let (remainder, content) = alt((
-        preceded(
-            peek(not(jobs_inline_section)),
+    // Sections can mix blocks of different kinds of entries, e.g. key-value pairs followed by text.
+    let (remainder, content) = map(
+        many0(preceded(
+            peek(not(alt((
+                value((), jobs_inline_section),
+                value(
+                    (),
+                    common::multispaced0(alt((
+                        tag(PROFILES.raw_name),
+                        tag(OWNERSHIP_INFO.raw_name),
+                    ))),
+                ),
+            )))),
             common::multispaced0(alt((
-                map(generic_table, |table| vec![InfoEntry::GenericTable(table)]),
+                map(generic_table, |table| {
+                    vec![InfoEntry::GenericTable(Rc::new(table))]
+                }),
                 many1(common::multispaced0(common::key_maybe_enabled_value(false))),
                 many1(common::multispaced0(thread)),
                 map(remote_object, |ro| vec![InfoEntry::RemoteObject(ro)]),
@@ -255,6 +294,7 @@ fn info_section<'a>(depth: SectionLevel) -> impl FnMut(Span) -> IResult<Span, Se
                         peek(not(alt((
                             value((), common::section_header),
                             value((), subsection_header),
+                            value((), jobs_inline_section),
                             value((), section_with_indented_subsections(LocalMetrics)),
                             value((), section_with_indented_subsections(NotificationProfiles)),
                             // value((), section_with_indented_subsections(OwnershipInfo)), // TODO: Investigate, it causes parsers::android::tests::info_section_ok::blocked_threads failure
@@ -264,9 +304,9 @@ fn info_section<'a>(depth: SectionLevel) -> impl FnMut(Span) -> IResult<Span, Se
                     |s: Span| InfoEntry::Generic(s.fragment().to_string()),
                 ))),
             ))),
-        ),
-        success(vec![]),
-    ))(remainder)?;
+        )),
+        |blocks| blocks.concat(),
+    )(remainder)?;
 
     let (remainder, subsections) = match depth {
         SectionLevel::Base => alt((
@@ -275,16 +315,16 @@ fn info_section<'a>(depth: SectionLevel) -> impl FnMut(Span) -> IResult<Span, Se
                 LocalMetrics,
             ))),
             subsection_with_indented_subsections(
-                "Profiles:",
-                "Profiles",
-                "No notification profiles",
-                NotificationProfiles,
+                PROFILES.raw_name,
+                PROFILES.name,
+                PROFILES.explicit_none,
+                PROFILES.ty,
             ),
             subsection_with_indented_subsections(
-                "Ownership Info:",
-                "Ownership Info",
-                "No ownership info to display.",
-                OwnershipInfo,
+                OWNERSHIP_INFO.raw_name,
+                OWNERSHIP_INFO.name,
+                OWNERSHIP_INFO.explicit_none,
+                OWNERSHIP_INFO.ty,
             ),
             success(vec![]),
         ))(remainder)?,
@@ -301,6 +341,13 @@ fn info_section<'a>(depth: SectionLevel) -> impl FnMut(Span) -> IResult<Span, Se
     ))
 }
 
+#[traceable_parser]
+fn level(input: Span) -> IResult<Span, LogLevel> {
+    map_opt(is_not(" "), |level: Span| {
+        AndroidFormat.parse_level(level.fragment())
+    })(input)
+}
+
 #[traceable_configurable_parser]
 fn logcat_entry<'a>(year: i32) -> impl FnMut(Span) -> IResult<Span, LogEntry> {
     map(
@@ -311,7 +358,7 @@ fn logcat_entry<'a>(year: i32) -> impl FnMut(Span) -> IResult<Span, LogEntry> {
             space0,
             is_not(" "),
             space0,
-            is_a("VDIWEF"),
+            level,
             space0,
             verify(
                 terminated(
@@ -325,7 +372,7 @@ fn logcat_entry<'a>(year: i32) -> impl FnMut(Span) -> IResult<Span, LogEntry> {
         )),
         |(dt, _, process_id, _, thread_id, _, level, _, tag, _, message)| LogEntry {
             timestamp: dt.to_string(),
-            level: Some(level.parse().unwrap()),
+            level: Some(level),
             meta: PlatformMetadata::AndroidLogcat {
                 process_id: process_id.fragment().to_string(),
                 thread_id: thread_id.fragment().to_string(),
@@ -409,7 +456,7 @@ fn logger_metadata(input: Span) -> IResult<Span, (PlatformMetadata, String, LogL
             space0,
             logger_timezone,
             space0,
-            is_not(" "),
+            level,
             space0,
             take_until(": "),
             tag(": "),
@@ -425,7 +472,7 @@ fn logger_metadata(input: Span) -> IResult<Span, (PlatformMetadata, String, LogL
                     LoggerTimezone::Parsed(tz) => tz.from_local_datetime(&dt).unwrap().to_string(),
                     LoggerTimezone::Unparsed(s) => dt.to_string() + " " + s,
                 },
-                level.parse().unwrap(),
+                level,
             )
         },
     )(input)
@@ -444,6 +491,239 @@ fn logger_entry(input: Span) -> IResult<Span, LogEntry> {
     )(input)
 }
 
+fn level_letter(level: Option<LogLevel>) -> &'static str {
+    match level.unwrap_or_default() {
+        LogLevel::Trace | LogLevel::Verbose => "V",
+        LogLevel::Debug => "D",
+        LogLevel::Info => "I",
+        LogLevel::Warn => "W",
+        LogLevel::Error => "E",
+        LogLevel::Fatal => "F",
+    }
+}
+
+/// Whether the section has exactly the key-value pairs of a local metric, in which case it's
+/// written as its name followed by indented lines.
+fn is_local_metric(section: &Section<InfoEntry>) -> bool {
+    section
+        .content
+        .iter()
+        .map(|entry| match entry {
+            InfoEntry::KeyValue(key, _) => Some(key.as_str()),
+            _ => None,
+        })
+        .collect::<Option<Vec<_>>>()
+        == Some(LocalMetrics.supported_section_keys())
+}
+
+fn write_indented_section(section: &Section<InfoEntry>, indentation: usize) -> String {
+    let indent = " ".repeat(indentation);
+
+    format!("{indent}{}\n", section.name)
+        + &section
+            .content
+            .iter()
+            .map(|entry| format!("{indent}  {}", writer::info_entry(entry)))
+            .collect::<String>()
+        + &section
+            .subsections
+            .iter()
+            .map(|subsection| write_indented_section(subsection, indentation + 4))
+            .collect::<String>()
+}
+
+fn write_listed_subsection(listed: &ListedSubsection, section: &Section<InfoEntry>) -> String {
+    let explicit_none = if section.content.contains(&InfoEntry::ExplicitNone) {
+        format!("    {}\n", listed.explicit_none)
+    } else {
+        String::new()
+    };
+
+    format!("{}\n{explicit_none}", listed.raw_name)
+        + &section
+            .subsections
+            .iter()
+            .map(|subsection| write_indented_section(subsection, 4))
+            .collect::<String>()
+}
+
+fn write_inline_section(section: &Section<InfoEntry>) -> String {
+    format!("id: {}", section.name)
+        + &section
+            .content
+            .iter()
+            .filter_map(writer::key_value)
+            .map(|pair| format!(" | {pair}"))
+            .collect::<String>()
+        + "\n"
+}
+
+fn write_info_section(section: &Section<InfoEntry>, depth: SectionLevel) -> String {
+    let header = match depth {
+        SectionLevel::Base => writer::section_header(&section.name),
+        SectionLevel::Sub => format!("--- {}\n", section.name),
+    };
+
+    let content = section
+        .content
+        .iter()
+        .map(writer::info_entry)
+        .collect::<String>();
+
+    let subsections = section
+        .subsections
+        .iter()
+        .map(|subsection| match depth {
+            SectionLevel::Base => match LISTED_SUBSECTIONS
+                .iter()
+                .find(|listed| listed.name == subsection.name)
+            {
+                Some(listed) => write_listed_subsection(listed, subsection),
+                None if is_local_metric(subsection) => write_indented_section(subsection, 0),
+                None => write_info_section(subsection, SectionLevel::Sub),
+            },
+            SectionLevel::Sub => write_inline_section(subsection),
+        })
+        .collect::<String>();
+
+    header + &content + &subsections + "\n"
+}
+
+fn write_log_entry(entry: &LogEntry) -> String {
+    let level = level_letter(entry.level);
+
+    match &entry.meta {
+        PlatformMetadata::AndroidLogcat {
+            process_id,
+            thread_id,
+            tag,
+        } => {
+            // Logcat timestamps don't include the year.
+            let (date_time, _) = writer::reformat_timestamp(&entry.timestamp, "%m-%d %H:%M:%S%.3f");
+
+            writer::lines_with_metadata(
+                &format!("{date_time} {process_id} {thread_id} {level} {tag}: "),
+                &entry.message,
+            )
+        }
+        PlatformMetadata::AndroidLogger {
+            version,
+            thread_id,
+            tag,
+        } => {
+            let (date_time, time_zone) =
+                writer::reformat_timestamp(&entry.timestamp, "%Y-%m-%d %H:%M:%S%.3f");
+
+            format!(
+                "[{version}] [{thread_id}] {date_time} {time_zone} {level} {tag}: {}\n",
+                entry.message
+            )
+        }
+        // Entries of other formats don't have the metadata that this one requires.
+        _ => String::new(),
+    }
+}
+
+fn write_log_section(section: &Section<LogEntry>) -> String {
+    let entries = |section: &Section<LogEntry>| {
+        section
+            .content
+            .iter()
+            .map(write_log_entry)
+            .collect::<String>()
+    };
+
+    writer::section_header(&section.name)
+        + &entries(section)
+        + &section
+            .subsections
+            .iter()
+            .map(|subsection| format!("--------- {}\n", subsection.name) + &entries(subsection))
+            .collect::<String>()
+}
+
+pub const LOGCAT_COLUMNS: &[Column] = &[
+    Column::new("Process"),
+    Column::with_role("Thread", ColumnRole::Thread),
+    Column::with_role("Tag", ColumnRole::Tag),
+];
+
+const LOGGER_COLUMNS: &[Column] = &[
+    Column::new("Version"),
+    Column::with_role("Thread", ColumnRole::Thread),
+    Column::with_role("Tag", ColumnRole::Tag),
+];
+
+/// Sections of information followed by logcat and the app's own logger entries.
+pub struct AndroidFormat;
+
+impl LogFormat for AndroidFormat {
+    fn id(&self) -> &'static str {
+        "android"
+    }
+
+    fn name(&self) -> &'static str {
+        "Android"
+    }
+
+    fn platform(&self) -> Option<Platform> {
+        Some(Platform::Android)
+    }
+
+    fn detect(&self, text: &str) -> bool {
+        format::has_section(text, LOGGER_SECTION_NAME)
+    }
+
+    fn parse(&self, text: &str) -> anyhow::Result<Content> {
+        format::parse_with(content, text)
+    }
+
+    fn write(&self, content: &Content) -> String {
+        content
+            .information
+            .iter()
+            .map(|section| write_info_section(section, SectionLevel::Base))
+            .chain(content.logs.iter().map(write_log_section))
+            .collect()
+    }
+
+    fn metadata_columns(&self) -> &'static [Column] {
+        LOGGER_COLUMNS
+    }
+
+    fn levels(&self) -> &'static [LogLevel] {
+        &[
+            LogLevel::Verbose,
+            LogLevel::Debug,
+            LogLevel::Info,
+            LogLevel::Warn,
+            LogLevel::Error,
+            LogLevel::Fatal,
+        ]
+    }
+
+    fn parse_level(&self, level: &str) -> Option<LogLevel> {
+        match level {
+            "V" => Some(LogLevel::Verbose),
+            "D" => Some(LogLevel::Debug),
+            "I" => Some(LogLevel::Info),
+            "W" => Some(LogLevel::Warn),
+            "E" => Some(LogLevel::Error),
+            "F" => Some(LogLevel::Fatal),
+            _ => None,
+        }
+    }
+
+    fn has_jobs(&self) -> bool {
+        true
+    }
+
+    fn is_launch_marker(&self, entry: &LogEntry) -> bool {
+        matches!(&entry.meta, PlatformMetadata::AndroidLogger { tag, .. } if tag == "ApplicationContext")
+            && entry.message.starts_with("onCreate()")
+    }
+}
+
 #[traceable_parser]
 pub fn content(input: Span) -> IResult<Span, Content> {
     let (remainder, (information, logcat_section, _, mut logger_entries)) = tuple((
@@ -478,7 +758,7 @@ mod tests {
     use test_case::test_case;
 
     use super::*;
-    use crate::{test_parsing, test_parsing_err_or_remainder};
+    use crate::{test_parsing, test_parsing_err_or_remainder, Platform};
 
     #[test_case("-- Abc", "Abc"; "basic")]
     #[test_case("--------- Long line", "Long line"; "long")]
@@ -916,26 +1196,26 @@ mod tests {
                 Section {
                     name: "Recipients".to_owned(),
                     content: vec![
-                        InfoEntry::GenericTable(GenericTable {
+                        InfoEntry::GenericTable(Rc::new(GenericTable {
                             header: vec!["_id".to_owned(), "old_id".to_owned(), "new_id".to_owned()],
                             rows: vec![
                                 vec!["1".to_owned(), "23".to_owned(), "456".to_owned()],
                                 vec!["2".to_owned(), "345".to_owned(), "678".to_owned()],
                             ]
-                        }),
+                        })),
                     ],
                     subsections: vec![],
                 },
                 Section {
                     name: "Threads".to_owned(),
                     content: vec![
-                        InfoEntry::GenericTable(GenericTable {
+                        InfoEntry::GenericTable(Rc::new(GenericTable {
                             header: vec!["_id".to_owned(), "old_id".to_owned(), "new_id".to_owned()],
                             rows: vec![
                                 vec!["3".to_owned(), "45".to_owned(), "678".to_owned()],
                                 vec!["4".to_owned(), "567".to_owned(), "890".to_owned()],
                             ]
-                        }),
+                        })),
                     ],
                     subsections: vec![],
                 }
@@ -950,6 +1230,33 @@ mod tests {
             subsections: vec![],
         }; "empty section"
     )]
+    #[test_case(
+        "===== MIXED =====\nkey: value\nflag: enabled\nSome free text\nmore: text",
+        Section {
+            name: "MIXED".to_owned(),
+            content: vec![
+                InfoEntry::KeyValue("key".to_owned(), Value::Generic("value".to_owned())),
+                InfoEntry::KeyEnabledValue("flag".to_owned(), true, None),
+                InfoEntry::Generic("Some free text".to_owned()),
+                InfoEntry::Generic("more: text".to_owned()),
+            ],
+            subsections: vec![],
+        }; "key-value pairs followed by text"
+    )]
+    #[test_case(
+        "===== TABLE AND KEYS =====\n| a | b |\n|---|---|\n| 1 | 2 |\n\nTotal: 1",
+        Section {
+            name: "TABLE AND KEYS".to_owned(),
+            content: vec![
+                InfoEntry::GenericTable(Rc::new(GenericTable {
+                    header: vec!["a".to_owned(), "b".to_owned()],
+                    rows: vec![vec!["1".to_owned(), "2".to_owned()]],
+                })),
+                InfoEntry::KeyValue("Total".to_owned(), Value::Generic("1".to_owned())),
+            ],
+            subsections: vec![],
+        }; "table followed by key-value pairs"
+    )]
     fn info_section_ok(input: &str, output: Section<InfoEntry>) {
         test_parsing(info_section(SectionLevel::Base), input, "", output);
     }
@@ -1184,4 +1491,30 @@ mod tests {
             }
         );
     }
+
+    const TEST_LOGS: &str = "========= LOGCAT ==========\n--------- beginning of crash\n01-21 12:34:56.789  1234  5678 F libc    : Fatal signal 11 (SIGSEGV)\n--------- beginning of main\n01-24 12:34:56.789 12345 12367 I V...@... MSG_WINDOW_FOCUS_CHANGED 1 1\n01-24 12:34:56.789 12345 12367 I V...@... MSG_WINDOW_FOCUS_CHANGED 1 1\n============ LOGGER =============\n[1.23.4] [5678 ] 1234-01-23 12:34:56.789 GMT+01:00 I abc: Log message\nspanning two lines\n[1.23.4] [main] 1234-01-23 12:34:56.790 GMT+01:00 W abc: Log message 2";
+
+    #[test_case(""; "logs only")]
+    #[test_case("========== JOBS ===========\n-- Jobs\nid: JOB::abcd1234-efgh-5678-ijkl-9012mnop1234 | a: TestJob | b: _test_value_ | number: 123 | negative: -1\n\n-- Constraints\njobSpecId: JOB::abcd1234-efgh-5678-ijkl-9012mnop1234 | a: TestConstraint | anotherValue: false\n\n-- Dependencies\nNone"; "jobs")]
+    #[test_case("====== HEADER =======\n-- Abc\nABC123          : true\nCapability Name : false\n\n-- Def\nABC123          : SUPPORTED\nCapability Name : NOT_SUPPORTED\nexample.testFlag: 1:2,3:4,*:5"; "bucketed flags")]
+    #[test_case("====== LOCAL METRICS ======\ncold-start-conversation-list\n  count: 5\n  p50: 3456\n  p90: 4567\n  p99: 4567\n    application-create\n      p50: 123\n      p90: 456\n      p99: 456\n    data-loaded\n      p50: 456\n      p90: 789\n      p99: 789\n\n\nconversation-open\n  count: 123\n  p50: 1234\n  p90: 5678\n  p99: 12345"; "local metrics")]
+    #[test_case("========== POWER ==========\nCurrent bucket: Frequent\nHighest bucket: Active\nLowest bucket : Rare\n\nMon Jan 23 12:34:56 GMT+01:00 1234: Bucket Change: Active\nMon Jan 23 12:34:57 GMT+01:00 1234: Bucket Change: Rare\nMon Jan 23 12:34:58 GMT+01:00 1234: Bucket Change: Frequent"; "generic lines")]
+    #[test_case("===== NOTIFICATION PROFILES =====\nManually enabled profile: 0\nManually enabled until  : 0\nManually disabled at    : 1234567890123\nNow                     : 1234567890321\n\nProfiles:\n    Profile 1\n    allowMentions   : false\n    allowCalls      : false\n    schedule enabled: false\n    schedule start  : 900\n    schedule end    : 2100\n    schedule days   : [MONDAY, TUESDAY, WEDNESDAY, THURSDAY, FRIDAY]"; "notification profiles")]
+    #[test_case("===== NOTIFICATION PROFILES =====\nManually enabled profile: 0\nManually enabled until  : 0\nManually disabled at    : 1234567890123\nNow                     : 1234567890321\n\nProfiles:\n    No notification profiles"; "no notification profiles")]
+    #[test_case("======== EXOPLAYER POOL =========\nTotal players created: 0\nMax allowed unreserved instances: 12\nMax allowed reserved instances: 1\nAvailable created unreserved instances: 0\nAvailable created reserved instances: 0\nTotal unreserved created: 0\nTotal reserved created: 0\n\nOwnership Info:\n  Owner abc def\n    reserved: 12\n    unreserved: 1\n  Owner abc def ghi\n    reserved: 5\n    unreserved: 4\n"; "ownership info")]
+    #[test_case("========== TRACE ==========\nhttps://debuglogs.org/0123456789abcdefabcd0123456789abcdefabcd0123456789abcdefabcd0123"; "remote object")]
+    #[test_case("========= THREADS =========\n[1] main\n[1234] Signal Catcher\n[1235] AbcDefGhi\n[6789] OkHttp https://abc-def.example.org/..."; "threads")]
+    #[test_case("======= LAST THREAD DUMP ========\nTime: 2009-02-13 11:31:30.123 GMT (1234567890123)\n\n-- [9876] AbcDefGhi (BLOCKED)\nghi.jkl.ABCdef.abcDefGhi(Native Method)\nabc.def.Abc$Cba.run(DEF.java:456)\nabc.def.Def.run(ABC.java:123)"; "thread dump")]
+    #[test_case("===== REMAPPED RECORDS =====\n--- Recipients\n\n| _id | old_id | new_id |\n|-----|--------|--------|\n| 1   | 23     | 456    |\n| 2   | 345    | 678    |\n\n--- Threads\n\n| _id | old_id | new_id |\n|-----|--------|--------|\n| 3   | 45     | 678    |\n| 4   | 567    | 890    |"; "tables")]
+    #[test_case("===== MIXED =====\nkey: value\nflag: enabled\nSome free text\nmore: text"; "mixed")]
+    fn write_round_trip(information: &str) {
+        let parsed = AndroidFormat
+            .parse(&format!("{information}\n{TEST_LOGS}"))
+            .unwrap();
+
+        assert_eq!(
+            AndroidFormat.parse(&AndroidFormat.write(&parsed)).unwrap(),
+            parsed
+        );
+    }
 }
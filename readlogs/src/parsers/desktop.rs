@@ -9,7 +9,7 @@ use nom::{
     IResult,
 };
 
-use crate::parsers::*;
+use crate::{parsers::*, LogLevel, Platform};
 
 const LOGS_SECTION_NAME: &str = "Logs";
 
@@ -47,7 +47,7 @@ fn level(input: Span) -> IResult<Span, LogLevel> {
         tag(LogLevel::Fatal.to_string().to_uppercase().as_str()),
     ))(input)?;
 
-    Ok((remainder, s.parse().unwrap()))
+    Ok((remainder, DesktopFormat.parse_level(&s).unwrap()))
 }
 
 #[traceable_parser]
@@ -75,6 +75,93 @@ fn log_entry(input: Span) -> IResult<Span, LogEntry> {
     )(input)
 }
 
+fn write_info_section(section: &Section<InfoEntry>) -> String {
+    writer::section_header(&section.name)
+        + &section
+            .content
+            .iter()
+            .map(writer::info_entry)
+            .collect::<String>()
+        + "\n"
+}
+
+fn write_log_entry(entry: &LogEntry) -> String {
+    let (date_time, _) = writer::reformat_timestamp(&entry.timestamp, "%Y-%m-%dT%H:%M:%S%.3fZ");
+
+    format!(
+        "{} {date_time} {}\n",
+        entry.level.unwrap_or_default().to_string().to_uppercase(),
+        entry.message
+    )
+}
+
+/// Sections of information followed by the log entries of the main and renderer processes.
+pub struct DesktopFormat;
+
+impl LogFormat for DesktopFormat {
+    fn id(&self) -> &'static str {
+        "desktop"
+    }
+
+    fn name(&self) -> &'static str {
+        "Desktop"
+    }
+
+    fn platform(&self) -> Option<Platform> {
+        Some(Platform::Desktop)
+    }
+
+    fn detect(&self, text: &str) -> bool {
+        format::has_section(text, LOGS_SECTION_NAME)
+    }
+
+    fn parse(&self, text: &str) -> anyhow::Result<Content> {
+        format::parse_with(content, text)
+    }
+
+    fn write(&self, content: &Content) -> String {
+        content
+            .information
+            .iter()
+            .map(write_info_section)
+            .chain(content.logs.iter().map(|section| {
+                writer::section_header(&section.name)
+                    + &section
+                        .content
+                        .iter()
+                        .map(write_log_entry)
+                        .collect::<String>()
+            }))
+            .collect()
+    }
+
+    fn metadata_columns(&self) -> &'static [Column] {
+        &[]
+    }
+
+    fn levels(&self) -> &'static [LogLevel] {
+        &[
+            LogLevel::Trace,
+            LogLevel::Debug,
+            LogLevel::Info,
+            LogLevel::Warn,
+            LogLevel::Error,
+            LogLevel::Fatal,
+        ]
+    }
+
+    fn parse_level(&self, level: &str) -> Option<LogLevel> {
+        self.levels()
+            .iter()
+            .copied()
+            .find(|variant| variant.to_string().to_uppercase() == level)
+    }
+
+    fn is_launch_marker(&self, entry: &LogEntry) -> bool {
+        entry.message.starts_with("starting version")
+    }
+}
+
 #[traceable_parser]
 pub fn content(input: Span) -> IResult<Span, Content> {
     let (remainder, (information, logs)) = separated_pair(
@@ -186,4 +273,14 @@ mod tests {
             }
         );
     }
+
+    #[test]
+    fn write_ok() {
+        let text = "========= Section 1 =========\nKey: 123.456 value\nAnother key: disabled\n\n========= Section 2 =========\nbucketed: enabled 1:2,3:4,*:5\n\n========= Logs =========\nINFO 1234-01-23T12:34:56.789Z This is a test message\nspanning two lines.\nDEBUG 1234-01-23T12:34:56.987Z Another message.\n";
+
+        assert_eq!(
+            DesktopFormat.write(&DesktopFormat.parse(text).unwrap()),
+            text
+        );
+    }
 }
@@ -0,0 +1,231 @@
+use std::fmt;
+
+use anyhow::{anyhow, Context};
+use nom::{combinator::eof, sequence::terminated, IResult};
+
+use crate::{
+    parsers::{android, common, desktop, ios, Content, LogEntry, Span},
+    span, LogLevel, Platform,
+};
+
+/// A format of debug log files, which knows how to recognize, parse and write them.
+///
+/// Formats are registered in [`FORMATS`]. Log entries of formats whose metadata isn't one of the
+/// built-in [`PlatformMetadata`](super::PlatformMetadata) variants can use
+/// `PlatformMetadata::Other`, which carries the format's columns.
+pub trait LogFormat: Sync {
+    /// Identifies the format among the registered ones.
+    fn id(&self) -> &'static str;
+
+    fn name(&self) -> &'static str;
+
+    /// The platform whose debug logs are in the format, if it's one of the built-in ones.
+    fn platform(&self) -> Option<Platform> {
+        None
+    }
+
+    /// Whether the text looks like it's in the format, which doesn't guarantee that it parses.
+    fn detect(&self, text: &str) -> bool;
+
+    fn parse(&self, text: &str) -> anyhow::Result<Content>;
+
+    /// The content as text in the format, which `parse` parses back into the same content (apart
+    /// from what the format doesn't record, e.g. the year of Android logcat entries).
+    fn write(&self, content: &Content) -> String;
+
+    /// Metadata columns of the entries in the main log section (e.g. the app's own logger rather
+    /// than logcat on Android), in the order they're displayed in. Entries of other sections have
+    /// their own columns (see [`PlatformMetadata::column_names`](super::PlatformMetadata::column_names)).
+    fn metadata_columns(&self) -> &'static [Column];
+
+    /// Levels that the format's log levels are mapped to, from the least important one.
+    fn levels(&self) -> &'static [LogLevel];
+
+    /// The level that the format writes as `level`, if any.
+    fn parse_level(&self, level: &str) -> Option<LogLevel>;
+
+    /// Whether files in the format have sections of information before their logs.
+    fn has_information(&self) -> bool {
+        true
+    }
+
+    /// Whether the app logs the jobs that it runs in the format.
+    fn has_jobs(&self) -> bool {
+        false
+    }
+
+    /// Whether the entry is logged by the app when it (re)starts.
+    fn is_launch_marker(&self, _entry: &LogEntry) -> bool {
+        false
+    }
+}
+
+impl fmt::Debug for dyn LogFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.id())
+    }
+}
+
+/// A metadata column of log entries.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Column {
+    pub name: &'static str,
+    pub role: Option<ColumnRole>,
+}
+
+impl Column {
+    pub const fn new(name: &'static str) -> Self {
+        Self { name, role: None }
+    }
+
+    pub const fn with_role(name: &'static str, role: ColumnRole) -> Self {
+        Self {
+            name,
+            role: Some(role),
+        }
+    }
+}
+
+/// What the values of a metadata column are, for columns that the app relies on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColumnRole {
+    /// Entries are grouped into swimlanes by it.
+    Thread,
+    /// Patterns can be grouped by it.
+    Tag,
+}
+
+/// Every supported format, in the order they're tried in when detecting the format of a file.
+pub static FORMATS: &[&dyn LogFormat] = &[
+    &android::AndroidFormat,
+    &desktop::DesktopFormat,
+    &ios::IosFormat,
+];
+
+/// The first format that the text looks like it's in.
+pub fn detect(text: &str) -> Option<&'static dyn LogFormat> {
+    FORMATS.iter().copied().find(|format| format.detect(text))
+}
+
+/// The format of debug logs from the platform, or else the one detected from the text.
+pub fn select(platform: Platform, text: &str) -> Option<&'static dyn LogFormat> {
+    FORMATS
+        .iter()
+        .copied()
+        .find(|format| format.platform() == Some(platform))
+        .or_else(|| detect(text))
+}
+
+/// Selects the format of the text (from the platform that it was submitted from) and parses it.
+pub fn parse(platform: Platform, text: &str) -> anyhow::Result<(&'static dyn LogFormat, Content)> {
+    let format =
+        select(platform, text).ok_or_else(|| anyhow!("the format of the file isn't supported"))?;
+
+    let content = format
+        .parse(text)
+        .with_context(|| format!("failed to parse {} debug log file", format.name()))?;
+
+    Ok((format, content))
+}
+
+/// Whether any line of the text is the header of a section with the name.
+pub fn has_section(text: &str, name: &str) -> bool {
+    // Only lines that start like a header are parsed, and the search stops at the first match.
+    text.lines()
+        .filter(|line| line.starts_with('='))
+        .any(|line| matches!(common::section_header(span(line)), Ok((_, header)) if header == name))
+}
+
+/// Parses the whole text with the parser of a format.
+pub fn parse_with(
+    parser: impl FnMut(Span) -> IResult<Span, Content>,
+    text: &str,
+) -> anyhow::Result<Content> {
+    let (_, output) =
+        terminated(parser, eof)(span(text)).map_err(|error| anyhow!("{:#?}", error))?;
+
+    Ok(output)
+}
+
+#[cfg(test)]
+pub use tests::test_format;
+
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+
+    use super::*;
+    use crate::parsers::PlatformMetadata;
+
+    pub fn test_format(id: &str) -> &'static dyn LogFormat {
+        FORMATS
+            .iter()
+            .copied()
+            .find(|format| format.id() == id)
+            .unwrap()
+    }
+
+    #[test_case("========= LOGCAT =========\n========= LOGGER =========\n" => Some("android"); "android")]
+    #[test_case("========= System info =========\nKey: Value\n========= Logs =========\nINFO 2023-01-22T12:00:00.000Z Message\n" => Some("desktop"); "desktop")]
+    #[test_case("2023/01/22 12:00:00:000 💛 [File.swift:12 symbol]: Message\n" => Some("ios"); "ios")]
+    #[test_case("\n" => Some("ios"); "ios empty")]
+    #[test_case("Not a debug log\n" => None; "unsupported")]
+    fn detect_by_text(text: &str) -> Option<&'static str> {
+        detect(text).map(|format| format.id())
+    }
+
+    #[test_case(Platform::Android, "\n" => Some("android"); "platform over text")]
+    #[test_case(Platform::Ios, "========= LOGCAT =========\n========= LOGGER =========\n" => Some("ios"); "platform over other format")]
+    fn select_by_platform(platform: Platform, text: &str) -> Option<&'static str> {
+        select(platform, text).map(|format| format.id())
+    }
+
+    #[test_case("========= LOGCAT =========\n========= LOGGER =========\n", "LOGGER" => true; "header")]
+    #[test_case("Message about ========= LOGGER =========\n", "LOGGER" => false; "not at the start of the line")]
+    #[test_case("========= LOGCAT =========\n", "LOGGER" => false; "other header")]
+    fn has_section_ok(text: &str, name: &str) -> bool {
+        has_section(text, name)
+    }
+
+    #[test]
+    fn ids_are_unique() {
+        for (index, format) in FORMATS.iter().enumerate() {
+            assert!(FORMATS[..index]
+                .iter()
+                .all(|other| other.id() != format.id()));
+        }
+    }
+
+    #[test]
+    fn other_metadata() {
+        const COLUMNS: &[Column] = &[
+            Column::with_role("Category", ColumnRole::Tag),
+            Column::with_role("Queue", ColumnRole::Thread),
+        ];
+
+        let meta = PlatformMetadata::Other {
+            columns: COLUMNS,
+            fields: vec!["Tag".to_owned(), "main".to_owned()],
+        };
+
+        assert_eq!(meta.column_names(), vec!["Category", "Queue"]);
+        assert_eq!(meta.fields(), vec!["Tag", "main"]);
+        assert_eq!(meta.thread_id(), Some("main"));
+        assert_eq!(meta.tag(), Some("Tag"));
+    }
+
+    #[test]
+    fn levels_are_ordered() {
+        for format in FORMATS {
+            assert!(format.levels().windows(2).all(|pair| pair[0] < pair[1]));
+        }
+    }
+
+    #[test_case("android", "W" => Some(LogLevel::Warn); "android")]
+    #[test_case("android", "WARN" => None; "android unknown")]
+    #[test_case("desktop", "WARN" => Some(LogLevel::Warn); "desktop")]
+    #[test_case("ios", "🧡" => Some(LogLevel::Warn); "ios")]
+    fn parse_level(id: &str, level: &str) -> Option<LogLevel> {
+        test_format(id).parse_level(level)
+    }
+}
@@ -3,16 +3,22 @@ use nom::{
     branch::alt,
     bytes::complete::{is_a, is_not, tag, take_until},
     character::complete::{multispace0, space0},
-    combinator::{map, opt, verify},
+    combinator::{map, map_opt, opt, verify},
     multi::many0,
     sequence::{preceded, terminated, tuple},
     IResult,
 };
 
-use crate::{parsers::*, LogLevel};
+use crate::{parsers::*, LogLevel, Platform};
 
 const DEFAULT_LOGS_SECTION_NAME: &str = "Logs";
 
+const COLUMNS: &[Column] = &[
+    Column::with_role("File", ColumnRole::Tag),
+    Column::new("Line"),
+    Column::new("Symbol"),
+];
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct LogEntryMetadata {
     pub file: String,
@@ -22,8 +28,8 @@ pub struct LogEntryMetadata {
 
 #[traceable_parser]
 fn level(input: Span) -> IResult<Span, LogLevel> {
-    map(is_a("💙💚💛🧡❤️"), |heart: Span| {
-        heart.parse().unwrap()
+    map_opt(is_a("💙💚💛🧡❤️"), |heart: Span| {
+        IosFormat.parse_level(heart.fragment())
     })(input)
 }
 
@@ -78,6 +84,107 @@ fn log_entry(input: Span) -> IResult<Span, LogEntry> {
     )(input)
 }
 
+fn heart(level: LogLevel) -> &'static str {
+    match level {
+        LogLevel::Trace | LogLevel::Verbose => "💙",
+        LogLevel::Debug => "💚",
+        LogLevel::Info => "💛",
+        LogLevel::Warn => "🧡",
+        LogLevel::Error | LogLevel::Fatal => "❤️",
+    }
+}
+
+fn write_log_entry(entry: &LogEntry) -> String {
+    let (date_time, _) = writer::reformat_timestamp(&entry.timestamp, "%Y/%m/%d %H:%M:%S:%3f");
+
+    let level = match entry.level {
+        Some(level) => format!(" {}", heart(level)),
+        None => String::new(),
+    };
+
+    let meta = match &entry.meta {
+        PlatformMetadata::Ios(Some(meta)) if meta.symbol.is_empty() => {
+            format!(" [{}:{}]:", meta.file, meta.line)
+        }
+        PlatformMetadata::Ios(Some(meta)) => {
+            format!(" [{}:{} {}]:", meta.file, meta.line, meta.symbol)
+        }
+        _ => String::new(),
+    };
+
+    format!("{date_time}{level}{meta} {}\n", entry.message)
+}
+
+/// Log entries of one of the files in an iOS archive, without any information sections.
+pub struct IosFormat;
+
+impl LogFormat for IosFormat {
+    fn id(&self) -> &'static str {
+        "ios"
+    }
+
+    fn name(&self) -> &'static str {
+        "iOS"
+    }
+
+    fn platform(&self) -> Option<Platform> {
+        Some(Platform::Ios)
+    }
+
+    /// Files in iOS archives may be empty, but otherwise start with an entry.
+    fn detect(&self, text: &str) -> bool {
+        let text = text.trim_start();
+
+        text.is_empty() || metadata(span(text)).is_ok()
+    }
+
+    fn parse(&self, text: &str) -> anyhow::Result<Content> {
+        format::parse_with(content, text)
+    }
+
+    fn write(&self, content: &Content) -> String {
+        content
+            .logs
+            .iter()
+            .flat_map(|section| &section.content)
+            .map(write_log_entry)
+            .collect()
+    }
+
+    fn metadata_columns(&self) -> &'static [Column] {
+        COLUMNS
+    }
+
+    fn levels(&self) -> &'static [LogLevel] {
+        &[
+            LogLevel::Verbose,
+            LogLevel::Debug,
+            LogLevel::Info,
+            LogLevel::Warn,
+            LogLevel::Error,
+        ]
+    }
+
+    fn parse_level(&self, level: &str) -> Option<LogLevel> {
+        match level {
+            "💙" => Some(LogLevel::Verbose),
+            "💚" => Some(LogLevel::Debug),
+            "💛" => Some(LogLevel::Info),
+            "🧡" => Some(LogLevel::Warn),
+            "❤️" => Some(LogLevel::Error),
+            _ => None,
+        }
+    }
+
+    fn has_information(&self) -> bool {
+        false
+    }
+
+    fn is_launch_marker(&self, entry: &LogEntry) -> bool {
+        matches!(&entry.meta, PlatformMetadata::Ios(Some(meta)) if meta.symbol.contains("didFinishLaunchingWithOptions"))
+    }
+}
+
 #[traceable_parser]
 pub fn content(input: Span) -> IResult<Span, Content> {
     preceded(
@@ -269,4 +376,19 @@ mod tests {
             },
         )
     }
+
+    #[test]
+    fn write_ok() {
+        let text = "1234/01/23 12:34:56:789 💚 [Item.abc:123 -[Item handleSomething]]: Debug message that spans\nmultiple lines\n1234/01/23 12:34:56:987 💛 [path/to/item.abc:456]: Another message...\n1234/01/23 12:34:56:999 Just a message.\n";
+
+        assert_eq!(IosFormat.write(&IosFormat.parse(text).unwrap()), text);
+    }
+
+    #[test_case("1234/01/23 12:34:56:123  ❤️ [Item.abc:123 -[Item handleSomething]]: Test 1\n1234/01/23 12:34:56:789  -[Abc def]:123 test\n1234/01/23 12:34:56:987  💚 Test 2"; "no log level or meta")]
+    #[test_case("1234/01/23 12:34:56:789  ❤️ [Item.abc:123 -[Item handleSomething]]: \n1234/01/23 12:34:56:987 💛 Next message"; "no message")]
+    fn write_round_trip(text: &str) {
+        let parsed = IosFormat.parse(text).unwrap();
+
+        assert_eq!(IosFormat.parse(&IosFormat.write(&parsed)).unwrap(), parsed);
+    }
 }
@@ -1,23 +1,28 @@
+use std::rc::Rc;
+
 use chrono::prelude::*;
 use derive_more::Display;
 use nom::{
     branch::alt,
-    bytes::complete::{tag, take_until},
+    bytes::complete::{is_not, tag, take_until},
     character::complete::space1,
-    combinator::{rest, value},
+    combinator::{map, rest, value},
     sequence::{delimited, preceded, terminated},
     IResult,
 };
 
 use crate::{impl_from_str, parsers::*};
 
-#[derive(Debug, Display, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Debug, Display, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum AppId {
     Signal,
     #[display(fmt = "NSE")]
     NotificationServiceExtension,
     #[display(fmt = "SAE")]
     ShareAppExtension,
+    /// Bundle id of an unknown app or extension, e.g. of a fork.
+    #[display(fmt = "{}", _0)]
+    Other(String),
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -31,25 +36,38 @@ pub struct LogFilename {
     pub extension: String,
 }
 
+/// Name of a file in a Signal iOS debug log archive.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ArchiveFilename {
+    Log(Rc<LogFilename>),
+    /// A file or directory whose name couldn't be parsed as a `LogFilename`.
+    Other(String),
+}
+
 #[traceable_parser]
 fn app_id_with_space(input: Span) -> IResult<Span, AppId> {
-    preceded(
-        tag("org.whispersystems.signal"),
-        alt((
-            value(AppId::Signal, space1),
-            terminated(
-                alt((
-                    value(AppId::NotificationServiceExtension, tag(".SignalNSE")),
-                    value(
-                        AppId::NotificationServiceExtension,
-                        tag(".NotificationServiceExtension"),
-                    ),
-                    value(AppId::ShareAppExtension, tag(".shareextension")),
-                )),
-                space1,
-            ),
-        )),
-    )(input)
+    alt((
+        preceded(
+            tag("org.whispersystems.signal"),
+            alt((
+                value(AppId::Signal, space1),
+                terminated(
+                    alt((
+                        value(AppId::NotificationServiceExtension, tag(".SignalNSE")),
+                        value(
+                            AppId::NotificationServiceExtension,
+                            tag(".NotificationServiceExtension"),
+                        ),
+                        value(AppId::ShareAppExtension, tag(".shareextension")),
+                    )),
+                    space1,
+                ),
+            )),
+        ),
+        map(terminated(is_not(" /\n"), space1), |bundle_id: Span| {
+            AppId::Other(bundle_id.fragment().to_string())
+        }),
+    ))(input)
 }
 
 #[traceable_parser]
@@ -122,6 +140,11 @@ mod tests {
         AppId::NotificationServiceExtension
     )]
     #[test_case("org.whispersystems.signal.shareextension ", AppId::ShareAppExtension)]
+    #[test_case(
+        "org.whispersystems.signal.newextension ",
+        AppId::Other("org.whispersystems.signal.newextension".to_owned())
+    )]
+    #[test_case("org.example.signalfork ", AppId::Other("org.example.signalfork".to_owned()))]
     fn app_id_with_space_ok(input: &str, output: AppId) {
         test_parsing(app_id_with_space, input, "", output)
     }
@@ -1,20 +1,31 @@
-use anyhow::anyhow;
+use std::{collections::HashMap, rc::Rc};
+
 use chrono::prelude::*;
-use nom::{combinator::eof, sequence::terminated};
+use derive_more::IsVariant;
 pub use nom_tracable::{tracable_parser as traceable_parser, TracableInfo as TraceableInfo};
 pub use readlogs_macros::traceable_configurable_parser;
-use yew::prelude::*;
+use yew::{html::Scope, prelude::*};
 
 use crate::{
-    components::*, span, LogLevel, Platform, RemoteObject, RenderedLogSection, SearchQuery,
+    components::*,
+    deltas::{self, Anchor},
+    details::EntrySelection,
+    information, rich_text, span,
+    timeline::{self, TimelineEvent},
+    timezone::TimestampFormatter,
+    DisplayOptions, Highlighter, LogLevel, Model, Msg, RemoteObject, RenderedLogSection,
+    SearchQuery, SearchSummary,
 };
 
 mod android;
 mod common;
 mod desktop;
+pub mod format;
 mod ios;
 mod ios_filename;
+mod writer;
 
+pub use format::{Column, ColumnRole, LogFormat};
 pub use ios_filename::*;
 
 pub type Span<'a> = nom_locate::LocatedSpan<&'a str, TraceableInfo>;
@@ -32,13 +43,13 @@ pub struct Section<C> {
     pub subsections: Vec<Section<C>>,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, IsVariant)]
 pub enum InfoEntry {
     KeyValue(String, Value),
     KeyEnabledValue(String, bool, Option<Value>),
     RemoteObject(RemoteObject),
     ExplicitNone,
-    GenericTable(GenericTable),
+    GenericTable(Rc<GenericTable>),
     Generic(String),
 }
 
@@ -88,124 +99,161 @@ pub enum PlatformMetadata {
     },
     Ios(Option<ios::LogEntryMetadata>),
     Desktop,
+    /// Metadata of a format without a variant of its own.
+    Other {
+        columns: &'static [Column],
+        fields: Vec<String>,
+    },
 }
 
-impl Content {
-    pub fn parse(platform: Platform, text: &str) -> anyhow::Result<Self> {
-        let parsing_fn = match platform {
-            Platform::Android => android::content,
-            Platform::Ios => ios::content,
-            Platform::Desktop => desktop::content,
-        };
+/// What log sections are rendered with, which is the same for all sections of a file.
+pub struct LogViewContext<'a> {
+    pub format: &'a dyn LogFormat,
+    pub query: &'a SearchQuery,
+    pub highlighter: &'a Highlighter,
+    pub timestamps: &'a TimestampFormatter,
+    pub options: &'a DisplayOptions,
+    /// Log entry that the "since anchor" deltas are relative to, if any.
+    pub anchor: Option<&'a Anchor>,
+    /// Log entries shown in the detail drawer, if any.
+    pub selection: Option<&'a EntrySelection>,
+    pub link: &'a Scope<Model>,
+}
 
-        let (_, output) =
-            terminated(parsing_fn, eof)(span(text)).map_err(|error| anyhow!("{:#?}", error))?;
+/// Where a displayed log entry is among the displayed entries of all sections, which its row and
+/// matches are numbered by, for the row cursor and match navigation.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct EntryPosition {
+    pub row: usize,
+    /// Index of the first match in the entry, which its matches are numbered from.
+    pub first_match: usize,
+}
 
-        Ok(output)
+/// Path of a section for telling apart subsections with the same name, e.g. `Parent/Child`.
+pub fn section_path(parent: Option<&str>, name: &str) -> String {
+    match parent {
+        Some(parent) => format!("{parent}/{name}"),
+        None => name.to_owned(),
     }
+}
 
-    pub fn view_information(&self, platform: Platform) -> Html {
-        if self.information.is_empty() {
-            let text = match platform {
-                Platform::Ios => {
-                    "Signal iOS debug logs don't contain any dedicated information sections."
-                }
-                Platform::Android | Platform::Desktop => {
-                    "This file doesn't seem to contain any dedicated information sections."
-                }
-            };
+impl Content {
+    pub fn search_summary(
+        &self,
+        query: &SearchQuery,
+        highlighter: &Highlighter,
+        timestamps: &TimestampFormatter,
+    ) -> SearchSummary {
+        self.logs
+            .iter()
+            .map(|section| section.search_summary(query, highlighter, timestamps))
+            .fold(SearchSummary::default(), SearchSummary::merge)
+    }
 
-            html! {
-                <Message {text} />
-            }
-        } else {
-            html! { for self.information.iter().map(|section| section.view(TitleLevel::H2)) }
-        }
+    pub fn count_matches(
+        &self,
+        query: &SearchQuery,
+        highlighter: &Highlighter,
+        timestamps: &TimestampFormatter,
+    ) -> usize {
+        self.logs
+            .iter()
+            .map(|section| section.count_matches(query, highlighter, timestamps))
+            .sum()
     }
 
-    pub fn view_logs(&self, query: &SearchQuery) -> Vec<RenderedLogSection> {
+    /// Levels of the log entries displayed with the query, in the order they're rendered in.
+    pub fn displayed_levels(
+        &self,
+        query: &SearchQuery,
+        highlighter: &Highlighter,
+        timestamps: &TimestampFormatter,
+    ) -> Vec<LogLevel> {
         self.logs
             .iter()
-            .map(|section| section.view(query, TitleLevel::H2))
+            .flat_map(|section| section.displayed_levels(query, highlighter, timestamps))
             .collect()
     }
-}
-
-impl Section<InfoEntry> {
-    pub fn view(&self, level: TitleLevel) -> Html {
-        let content = html! { for self.content.iter().map(|entry| entry.view()) };
 
-        // TODO: Assumes that all entries in the section are the same variant.
-        let wrapper = match self.content.first() {
-            Some(InfoEntry::KeyValue(_, _)) | Some(InfoEntry::KeyEnabledValue(_, _, _)) => html! {
-                <Table classes={classes!("font-mono", "text-sm")}>
-                    <tbody>
-                        { content }
-                    </tbody>
-                </Table>
-            },
-            Some(InfoEntry::Generic(_)) => html! {
-                <CodeBlock>
-                    { content }
-                </CodeBlock>
-            },
-            _ => content,
-        };
+    /// Positions of the entries displayed with the query, by the path of their section,
+    /// in the order they're rendered in.
+    pub fn entry_positions(
+        &self,
+        query: &SearchQuery,
+        highlighter: &Highlighter,
+        timestamps: &TimestampFormatter,
+    ) -> HashMap<String, Vec<EntryPosition>> {
+        let mut positions = HashMap::new();
+        let mut next = EntryPosition::default();
+
+        for section in &self.logs {
+            section.entry_positions(
+                query,
+                highlighter,
+                timestamps,
+                None,
+                &mut next,
+                &mut positions,
+            );
+        }
 
-        let full_content = if self.content.is_empty() && self.subsections.is_empty() {
-            html! {
-                <p><i>{ "No content or subsections" }</i></p>
-            }
-        } else {
-            html! {
-                <>
-                    { wrapper }
-                    { for self.subsections.iter().map(|section| section.view(level.incremented().unwrap())) }
-                </>
-            }
-        };
+        positions
+    }
 
-        let raw = level > TitleLevel::H2;
+    pub fn view_logs(&self, context: &LogViewContext) -> Vec<RenderedLogSection> {
+        let positions =
+            self.entry_positions(context.query, context.highlighter, context.timestamps);
 
-        html! {
-            <>
-                <Title {level} text={self.name.clone()} {raw} capitalize={!raw} />
-                { full_content }
-            </>
-        }
+        self.logs
+            .iter()
+            .map(|section| section.view(context, &positions, TitleLevel::H2, None))
+            .collect()
     }
 }
 
 impl InfoEntry {
-    pub fn view(&self) -> Html {
+    /// Row of a key-value table, which has an enabled column if any of its rows has `enabled`.
+    /// Other entries aren't rows, so they render nothing.
+    pub fn view_row(&self, enabled_column: bool) -> Html {
         match self {
             InfoEntry::KeyValue(key, value) => html! {
                 <TableRow>
                     <TableItem>{ key }</TableItem>
+                    {
+                        if enabled_column {
+                            html! { <TableItem /> }
+                        } else {
+                            html! {}
+                        }
+                    }
                     <TableItem>{ value.view() }</TableItem>
                 </TableRow>
             },
             InfoEntry::KeyEnabledValue(key, enabled, value) => html! {
                 <TableRow>
                     <TableItem>{ key }</TableItem>
-                    {
-                        // TODO: Assumes that all KV pairs in section have `enabled`
-                        //       (otherwise the table will be misaligned).
-                        html! {
-                            <TableItem>
-                                {
-                                    if *enabled {
-                                        "enabled"
-                                    } else {
-                                        "disabled"
-                                    }
-                                }
-                            </TableItem>
+                    <TableItem>
+                        {
+                            if *enabled {
+                                "enabled"
+                            } else {
+                                "disabled"
+                            }
                         }
-                    }
+                    </TableItem>
                     <TableItem>{ value.clone().unwrap_or_default().view() }</TableItem>
                 </TableRow>
             },
+            _ => html! {},
+        }
+    }
+
+    /// Renders the entry of the section with the path, whose tables are downloaded as a CSV file
+    /// named after it.
+    pub fn view(&self, path: &str) -> Html {
+        match self {
+            InfoEntry::KeyValue(_, _) => self.view_row(false),
+            InfoEntry::KeyEnabledValue(_, _, _) => self.view_row(true),
             InfoEntry::RemoteObject(ro) => html! {
                 <Button
                     size={ButtonSize::Medium}
@@ -216,30 +264,11 @@ impl InfoEntry {
             },
             InfoEntry::ExplicitNone => html! { <p>{ "None" }</p> },
             InfoEntry::GenericTable(table) => html! {
-                <Table>
-                    <thead>
-                        <TableRow>
-                            {
-                                for table.header.iter().map(|item| html! {
-                                    <TableItem>{ item }</TableItem>
-                                })
-                            }
-                        </TableRow>
-                    </thead>
-                    <tbody>
-                        {
-                            for table.rows.iter().map(|row| html! {
-                                <TableRow>
-                                    {
-                                        for row.iter().map(|item| html! {
-                                            <TableItem>{ item }</TableItem>
-                                        })
-                                    }
-                                </TableRow>
-                            })
-                        }
-                    </tbody>
-                </Table>
+                <SortableTable
+                    key={path}
+                    table={Rc::clone(table)}
+                    filename={format!("{}.csv", information::section_slug(path))}
+                />
             },
             InfoEntry::Generic(text) => html! { text.to_owned() + "\n" },
         }
@@ -279,24 +308,214 @@ impl Value {
 }
 
 impl Section<LogEntry> {
-    pub fn view(&self, query: &SearchQuery, level: TitleLevel) -> RenderedLogSection {
-        let s = &query.string.to_lowercase();
-        let entries_to_display = self
-            .content
+    /// Entries (along with their 1-based numbers) that match the query, in order.
+    pub fn displayed_entries<'a>(
+        &'a self,
+        query: &'a SearchQuery,
+        highlighter: &'a Highlighter,
+        timestamps: &'a TimestampFormatter,
+    ) -> impl Iterator<Item = (usize, &'a LogEntry)> + Clone + 'a {
+        self.content
             .iter()
             .enumerate()
             .map(|(index, entry)| (index + 1, entry))
             .filter(|(_, entry)| entry.level.unwrap_or_default() >= query.min_log_level)
+            .filter(|(_, entry)| query.tag.is_none() || entry.meta.tag() == query.tag.as_deref())
             .filter(|(_, entry)| {
-                entry.timestamp.to_lowercase().contains(s)
-                    || entry.message.to_lowercase().contains(s)
-                    || entry.meta.contains(s)
-            });
+                query
+                    .message_pattern
+                    .iter()
+                    .all(|pattern| pattern.is_match(&entry.message))
+            })
+            .filter(|(_, entry)| entry.is_match(highlighter, timestamps))
+    }
+
+    /// Adds the positions of the displayed entries of the section and its subsections, starting
+    /// from `next`.
+    fn entry_positions(
+        &self,
+        query: &SearchQuery,
+        highlighter: &Highlighter,
+        timestamps: &TimestampFormatter,
+        parent: Option<&str>,
+        next: &mut EntryPosition,
+        positions: &mut HashMap<String, Vec<EntryPosition>>,
+    ) {
+        let path = section_path(parent, &self.name);
+
+        let section_positions = self
+            .displayed_entries(query, highlighter, timestamps)
+            .map(|(_, entry)| {
+                let position = *next;
+                next.row += 1;
+                next.first_match += entry.count_matches(highlighter, timestamps);
+                position
+            })
+            .collect();
+
+        for subsection in &self.subsections {
+            subsection.entry_positions(
+                query,
+                highlighter,
+                timestamps,
+                Some(&path),
+                next,
+                positions,
+            );
+        }
+
+        positions.insert(path, section_positions);
+    }
+
+    pub fn search_summary(
+        &self,
+        query: &SearchQuery,
+        highlighter: &Highlighter,
+        timestamps: &TimestampFormatter,
+    ) -> SearchSummary {
+        self.displayed_entries(query, highlighter, timestamps)
+            .map(|(_, entry)| SearchSummary {
+                entries: 1,
+                max_level: entry.level,
+            })
+            .chain(
+                self.subsections
+                    .iter()
+                    .map(|subsection| subsection.search_summary(query, highlighter, timestamps)),
+            )
+            .fold(SearchSummary::default(), SearchSummary::merge)
+    }
+
+    pub fn displayed_levels(
+        &self,
+        query: &SearchQuery,
+        highlighter: &Highlighter,
+        timestamps: &TimestampFormatter,
+    ) -> Vec<LogLevel> {
+        self.displayed_entries(query, highlighter, timestamps)
+            .map(|(_, entry)| entry.level.unwrap_or_default())
+            .chain(
+                self.subsections.iter().flat_map(|subsection| {
+                    subsection.displayed_levels(query, highlighter, timestamps)
+                }),
+            )
+            .collect()
+    }
+
+    pub fn count_matches(
+        &self,
+        query: &SearchQuery,
+        highlighter: &Highlighter,
+        timestamps: &TimestampFormatter,
+    ) -> usize {
+        self.displayed_entries(query, highlighter, timestamps)
+            .map(|(_, entry)| entry.count_matches(highlighter, timestamps))
+            .sum::<usize>()
+            + self
+                .subsections
+                .iter()
+                .map(|subsection| subsection.count_matches(query, highlighter, timestamps))
+                .sum::<usize>()
+    }
+
+    /// Renders the entries displayed with the query, at the `positions` of the entries by path.
+    pub fn view(
+        &self,
+        context: &LogViewContext,
+        positions: &HashMap<String, Vec<EntryPosition>>,
+        level: TitleLevel,
+        parent: Option<&str>,
+    ) -> RenderedLogSection {
+        let LogViewContext {
+            format,
+            query,
+            highlighter,
+            timestamps,
+            options,
+            anchor,
+            selection,
+            link,
+        } = *context;
+        let path = section_path(parent, &self.name);
+        let entry_positions = positions.get(&path).map(Vec::as_slice).unwrap_or_default();
+
+        let entries_to_display = self.displayed_entries(query, highlighter, timestamps);
 
         let displayed_count = entries_to_display.clone().count();
         let total_count = self.content.len();
 
         let table = if displayed_count != 0 {
+            let column_names = self
+                .content
+                .first()
+                .map(|entry| entry.meta.column_names())
+                .unwrap_or_default();
+            let delta_columns = options.delta_columns;
+            let column_count = column_names.len() + 3 + delta_columns.count();
+
+            let since_previous = deltas::since_previous(
+                entries_to_display.clone().map(|(_, entry)| entry),
+                timestamps,
+            );
+
+            let mut events =
+                timeline::events(&self.content, format, options.gap_threshold, timestamps)
+                    .into_iter()
+                    .peekable();
+
+            let rows = entries_to_display
+                .zip(since_previous)
+                .zip(entry_positions)
+                .map(|(((number, entry), since_previous), position)| {
+                    // Events before entries that aren't displayed are shown before the next displayed one.
+                    let mut preceding_events = vec![];
+                    while let Some((_, events)) = events.next_if(|(index, _)| *index < number) {
+                        preceding_events.extend(events);
+                    }
+
+                    let deltas = html! {
+                        <>
+                            {
+                                if delta_columns.previous() {
+                                    deltas::view_delta(since_previous)
+                                } else {
+                                    html! {}
+                                }
+                            }
+                            {
+                                if delta_columns.anchor() {
+                                    deltas::view_since_anchor(&path, number, entry, anchor, timestamps, link)
+                                } else {
+                                    html! {}
+                                }
+                            }
+                        </>
+                    };
+
+                    let selected =
+                        matches!(selection, Some(selection) if selection.contains(&path, number));
+                    let section = path.clone();
+                    let on_click =
+                        link.batch_callback(
+                            move |event: MouseEvent| match rich_text::pivot_target(&event) {
+                                Some(identifier) => Some(Msg::Pivot(identifier)),
+                                None if rich_text::is_in_fold(&event) => None,
+                                None => Some(Msg::SelectEntry(
+                                    section.clone(),
+                                    number,
+                                    event.shift_key(),
+                                )),
+                            },
+                        );
+
+                    html! {
+                        <>
+                            { view_timeline_events(&preceding_events, column_count) }
+                            { entry.view(number, *position, context, deltas, selected, on_click) }
+                        </>
+                    }
+                });
+
             html! {
                 <Table>
                     <thead>
@@ -305,37 +524,31 @@ impl Section<LogEntry> {
                             <TableItem tag="th" classes={classes!("min-w-[250px]")}>{ "Timestamp" }</TableItem>
 
                             {
-                                match &self.content.get(0).unwrap().meta { // TODO: assumption?
-                                    PlatformMetadata::AndroidLogcat { .. } => html! {
-                                        <>
-                                            <TableItem tag="th">{ "Process" }</TableItem>
-                                            <TableItem tag="th">{ "Thread" }</TableItem>
-                                            <TableItem tag="th">{ "Tag" }</TableItem>
-                                        </>
-                                    },
-                                    PlatformMetadata::AndroidLogger { .. } => html! {
-                                        <>
-                                            <TableItem tag="th">{ "Version" }</TableItem>
-                                            <TableItem tag="th">{ "Thread" }</TableItem>
-                                            <TableItem tag="th">{ "Tag" }</TableItem>
-                                        </>
-                                    },
-                                    PlatformMetadata::Ios(_) => html! {
-                                        <>
-                                            <TableItem tag="th">{ "File" }</TableItem>
-                                            <TableItem tag="th">{ "Line" }</TableItem>
-                                            <TableItem tag="th">{ "Symbol" }</TableItem>
-                                        </>
-                                    },
-                                    PlatformMetadata::Desktop => html! {},
+                                if delta_columns.previous() {
+                                    html! { <TableItem tag="th" classes={classes!("whitespace-nowrap")}>{ "Δ previous" }</TableItem> }
+                                } else {
+                                    html! {}
+                                }
+                            }
+                            {
+                                if delta_columns.anchor() {
+                                    html! { <TableItem tag="th" classes={classes!("whitespace-nowrap")}>{ "Δ anchor" }</TableItem> }
+                                } else {
+                                    html! {}
                                 }
                             }
 
+                            {
+                                for column_names.into_iter().map(|name| html! {
+                                    <TableItem tag="th">{ name }</TableItem>
+                                })
+                            }
+
                             <TableItem tag="th">{ "Message" }</TableItem>
                         </TableRow>
                     </thead>
                     <tbody class="font-mono">
-                        { for entries_to_display.map(|(number, entry)| entry.view(number)) }
+                        { for rows }
                     </tbody>
                 </Table>
             }
@@ -346,7 +559,14 @@ impl Section<LogEntry> {
         let subsections = self
             .subsections
             .iter()
-            .map(|subsection| subsection.view(query, level.incremented().unwrap()))
+            .map(|subsection| {
+                subsection.view(
+                    context,
+                    positions,
+                    level.incremented().unwrap(),
+                    Some(&path),
+                )
+            })
             .collect();
 
         RenderedLogSection {
@@ -360,42 +580,191 @@ impl Section<LogEntry> {
     }
 }
 
+fn view_timeline_events(events: &[TimelineEvent], column_count: usize) -> Html {
+    if events.is_empty() {
+        return html! {};
+    }
+
+    let text = events
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    html! {
+        <TableRow classes={classes!("bg-brand-bg-message", "dark:bg-brand-dark-bg-message")}>
+            <TableItem colspan={column_count} classes={classes!("text-center", "font-sans", "italic")}>
+                <Icon icon={classes!("fas", "fa-history")} classes={classes!("mr-2")} />
+                { text }
+            </TableItem>
+        </TableRow>
+    }
+}
+
 impl LogEntry {
-    pub fn view(&self, number: usize) -> Html {
+    /// Whether the entry matches, with its timestamp as it's displayed and its message as it was
+    /// logged.
+    pub fn is_match(&self, highlighter: &Highlighter, timestamps: &TimestampFormatter) -> bool {
+        highlighter.is_match(&timestamps.displayed(&self.timestamp))
+            || highlighter.is_match(&self.message)
+            || self.meta.is_match(highlighter)
+    }
+
+    /// Number of matches in the order they're highlighted in by [`LogEntry::view`], i.e. in the
+    /// message as it's displayed.
+    pub fn count_matches(
+        &self,
+        highlighter: &Highlighter,
+        timestamps: &TimestampFormatter,
+    ) -> usize {
+        highlighter.count(&timestamps.displayed(&self.timestamp))
+            + self
+                .meta
+                .fields()
+                .iter()
+                .map(|field| highlighter.count(field))
+                .sum::<usize>()
+            + rich_text::count_matches(&self.message, highlighter)
+    }
+
+    /// Renders the entry's row, with its matches numbered as [`LogEntry::count_matches`] counts
+    /// them.
+    pub fn view(
+        &self,
+        number: usize,
+        position: EntryPosition,
+        context: &LogViewContext,
+        deltas: Html,
+        selected: bool,
+        on_click: Callback<MouseEvent>,
+    ) -> Html {
+        let LogViewContext {
+            highlighter,
+            timestamps,
+            ..
+        } = *context;
+
+        let meta_first_match =
+            position.first_match + highlighter.count(&timestamps.displayed(&self.timestamp));
+        let message_first_match = meta_first_match
+            + self
+                .meta
+                .fields()
+                .iter()
+                .map(|field| highlighter.count(field))
+                .sum::<usize>();
+
+        let mut classes = self.level.unwrap_or_default().color();
+        classes.push("cursor-pointer");
+        if highlighter.is_cursor(position.row) || selected {
+            classes.push(classes!(
+                "bg-brand-bg-message",
+                "dark:bg-brand-dark-bg-message"
+            ));
+        }
+
         html! {
-            <TableRow classes={self.level.unwrap_or_default().color()}>
+            <TableRow id={Highlighter::row_id(position.row)} {classes} {on_click}>
                 <TableItem>{ number }</TableItem>
-                <TableItem>{ self.timestamp.to_string() }</TableItem>
-                { self.meta.clone().view() }
-                <TableItem><pre>{ self.message.to_owned() }</pre></TableItem>
+                <TableItem>{ timestamps.view(&self.timestamp, highlighter, Some(position.first_match)) }</TableItem>
+                { deltas }
+                { self.meta.view(highlighter, Some(meta_first_match)) }
+                <TableItem><pre>{ rich_text::view(&self.message, highlighter, Some(message_first_match)) }</pre></TableItem>
             </TableRow>
         }
     }
 }
 
 impl PlatformMetadata {
-    pub fn contains(&self, s: &str) -> bool {
+    /// Metadata columns, in the order they're displayed in.
+    pub fn columns(&self) -> &'static [Column] {
+        match self {
+            PlatformMetadata::AndroidLogcat { .. } => android::LOGCAT_COLUMNS,
+            PlatformMetadata::AndroidLogger { .. } => android::AndroidFormat.metadata_columns(),
+            PlatformMetadata::Ios(_) => ios::IosFormat.metadata_columns(),
+            PlatformMetadata::Desktop => desktop::DesktopFormat.metadata_columns(),
+            PlatformMetadata::Other { columns, .. } => columns,
+        }
+    }
+
+    /// Names of the metadata columns, in the order they're displayed in.
+    pub fn column_names(&self) -> Vec<&'static str> {
+        self.columns().iter().map(|column| column.name).collect()
+    }
+
+    /// Value of the column of `Other` metadata, if it has one with the role.
+    fn other_field(&self, role: ColumnRole) -> Option<&str> {
+        match self {
+            PlatformMetadata::Other { columns, fields } => columns
+                .iter()
+                .position(|column| column.role == Some(role))
+                .and_then(|index| fields.get(index))
+                .map(String::as_str),
+            _ => None,
+        }
+    }
+
+    /// Values of the metadata columns, in the order they're displayed in.
+    pub fn fields(&self) -> Vec<&str> {
         match &self {
             PlatformMetadata::AndroidLogcat {
                 process_id,
                 thread_id,
                 tag,
-            } => process_id.contains(s) || thread_id.contains(s) || tag.contains(s),
+            } => vec![process_id, thread_id, tag],
             PlatformMetadata::AndroidLogger {
                 version,
                 thread_id,
                 tag,
-            } => version.contains(s) || thread_id.contains(s) || tag.contains(s),
-            PlatformMetadata::Ios(Some(meta)) => {
-                meta.file.to_lowercase().contains(s)
-                    || meta.line.to_lowercase().contains(s)
-                    || meta.symbol.to_lowercase().contains(s)
-            }
-            PlatformMetadata::Ios(None) | PlatformMetadata::Desktop => false,
+            } => vec![version, thread_id, tag],
+            PlatformMetadata::Ios(Some(meta)) => vec![&meta.file, &meta.line, &meta.symbol],
+            PlatformMetadata::Ios(None) | PlatformMetadata::Desktop => vec![],
+            PlatformMetadata::Other { fields, .. } => fields.iter().map(String::as_str).collect(),
+        }
+    }
+
+    pub fn thread_id(&self) -> Option<&str> {
+        match self {
+            PlatformMetadata::AndroidLogcat { thread_id, .. }
+            | PlatformMetadata::AndroidLogger { thread_id, .. } => Some(thread_id),
+            PlatformMetadata::Ios(_) | PlatformMetadata::Desktop => None,
+            PlatformMetadata::Other { .. } => self.other_field(ColumnRole::Thread),
+        }
+    }
+
+    /// Tag (or file, for iOS) that the entry was logged from, if any.
+    pub fn tag(&self) -> Option<&str> {
+        match self {
+            PlatformMetadata::AndroidLogcat { tag, .. }
+            | PlatformMetadata::AndroidLogger { tag, .. } => Some(tag),
+            PlatformMetadata::Ios(Some(meta)) => Some(&meta.file),
+            PlatformMetadata::Ios(None) | PlatformMetadata::Desktop => None,
+            PlatformMetadata::Other { .. } => self.other_field(ColumnRole::Tag),
         }
     }
 
-    pub fn view(self) -> Html {
+    pub fn is_match(&self, highlighter: &Highlighter) -> bool {
+        self.fields()
+            .iter()
+            .any(|field| !field.is_empty() && highlighter.is_match(field))
+    }
+
+    /// Renders the metadata columns, numbering their matches from `first_match` (in the order of
+    /// [`PlatformMetadata::fields`]) if they can be navigated between.
+    pub fn view(&self, highlighter: &Highlighter, first_match: Option<usize>) -> Html {
+        let mut next_match = first_match;
+        let first_matches = self
+            .fields()
+            .iter()
+            .map(|field| {
+                let first_match = next_match;
+                next_match = next_match.map(|next_match| next_match + highlighter.count(field));
+                first_match
+            })
+            .collect::<Vec<_>>();
+
+        let view = |index: usize, field: &str| highlighter.view(field, first_matches[index]);
+
         match self {
             PlatformMetadata::AndroidLogcat {
                 process_id,
@@ -403,9 +772,9 @@ impl PlatformMetadata {
                 tag,
             } => html! {
                 <>
-                    <TableItem>{ process_id }</TableItem>
-                    <TableItem>{ thread_id }</TableItem>
-                    <TableItem>{ tag }</TableItem>
+                    <TableItem>{ view(0, process_id) }</TableItem>
+                    <TableItem>{ view(1, thread_id) }</TableItem>
+                    <TableItem>{ view(2, tag) }</TableItem>
                 </>
             },
             PlatformMetadata::AndroidLogger {
@@ -414,16 +783,16 @@ impl PlatformMetadata {
                 tag,
             } => html! {
                 <>
-                    <TableItem>{ version }</TableItem>
-                    <TableItem>{ thread_id }</TableItem>
-                    <TableItem>{ tag }</TableItem>
+                    <TableItem>{ view(0, version) }</TableItem>
+                    <TableItem>{ view(1, thread_id) }</TableItem>
+                    <TableItem>{ view(2, tag) }</TableItem>
                 </>
             },
             PlatformMetadata::Ios(Some(meta)) => html! {
                 <>
-                    <TableItem>{ meta.file }</TableItem>
-                    <TableItem>{ meta.line }</TableItem>
-                    <TableItem classes={classes!("text-right")}><pre>{ meta.symbol }</pre></TableItem>
+                    <TableItem>{ view(0, &meta.file) }</TableItem>
+                    <TableItem>{ view(1, &meta.line) }</TableItem>
+                    <TableItem classes={classes!("text-right")}><pre>{ view(2, &meta.symbol) }</pre></TableItem>
                 </>
             },
             PlatformMetadata::Ios(None) => html! {
@@ -434,6 +803,139 @@ impl PlatformMetadata {
                 </>
             },
             PlatformMetadata::Desktop => html! {},
+            PlatformMetadata::Other { fields, .. } => html! {
+                { for fields.iter().enumerate().map(|(index, field)| html! { <TableItem>{ view(index, field) }</TableItem> }) }
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+pub use tests::test_entry;
+
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+
+    use super::*;
+    use crate::{
+        patterns::{MessagePattern, Templater},
+        DisplayTimezone,
+    };
+
+    pub fn test_entry(
+        timestamp: &str,
+        version: &str,
+        thread_id: &str,
+        tag: &str,
+        message: &str,
+    ) -> LogEntry {
+        LogEntry {
+            timestamp: timestamp.to_owned(),
+            level: Some(LogLevel::Info),
+            meta: PlatformMetadata::AndroidLogger {
+                version: version.to_owned(),
+                thread_id: thread_id.to_owned(),
+                tag: tag.to_owned(),
+            },
+            message: message.to_owned(),
+        }
+    }
+
+    fn test_section(
+        name: &str,
+        messages: &[&str],
+        subsections: Vec<Section<LogEntry>>,
+    ) -> Section<LogEntry> {
+        Section {
+            name: name.to_owned(),
+            content: messages
+                .iter()
+                .map(|message| test_entry("1", "1", "1", "T", message))
+                .collect(),
+            subsections,
         }
     }
+
+    #[test]
+    fn entry_positions_in_render_order() {
+        let content = Content {
+            information: vec![],
+            logs: vec![
+                test_section("A", &["aa", "b"], vec![test_section("Sub", &["a"], vec![])]),
+                test_section("B", &["xa a"], vec![]),
+            ],
+        };
+        let query = SearchQuery {
+            min_log_level: LogLevel::Info,
+            string: "a".to_owned(),
+            ..Default::default()
+        };
+
+        let positions = content.entry_positions(
+            &query,
+            &Highlighter::new(&query),
+            &TimestampFormatter::new(DisplayTimezone::Original, None),
+        );
+
+        let position = |row, first_match| EntryPosition { row, first_match };
+        assert_eq!(
+            positions,
+            HashMap::from([
+                ("A".to_owned(), vec![position(0, 0)]),
+                ("A/Sub".to_owned(), vec![position(1, 2)]),
+                ("B".to_owned(), vec![position(2, 3)]),
+            ])
+        );
+    }
+
+    #[test]
+    fn displayed_entries_by_message_pattern() {
+        let section = test_section(
+            "A",
+            &[
+                "Response {\"id\":12,\"ok\":true}",
+                "Response {\"id\":34,\"ok\":true}",
+                "Response {\"id\":56,\"ok\":false}",
+            ],
+            vec![],
+        );
+        let template = Templater::default().template(&section.content[0].message);
+        let query = SearchQuery {
+            min_log_level: LogLevel::Info,
+            message_pattern: Some(MessagePattern::new(template)),
+            ..Default::default()
+        };
+
+        let displayed = section
+            .displayed_entries(
+                &query,
+                &Highlighter::new(&query),
+                &TimestampFormatter::new(DisplayTimezone::Original, None),
+            )
+            .map(|(number, _)| number)
+            .collect::<Vec<_>>();
+
+        assert_eq!(displayed, vec![1, 2]);
+    }
+
+    #[test_case("Sent" => (true, 1); "text")]
+    #[test_case("\\{\"a\":1\\}" => (true, 0); "raw json")]
+    #[test_case("Sent.*done" => (true, 0); "across blocks")]
+    #[test_case("Received" => (false, 0); "no match")]
+    fn entry_is_match_and_count(string: &str) -> (bool, usize) {
+        let entry = test_entry("1", "1", "1", "T", "Sent {\"a\":1} done");
+        let query = SearchQuery {
+            string: string.to_owned(),
+            regex: true,
+            ..Default::default()
+        };
+        let highlighter = Highlighter::new(&query);
+        let timestamps = TimestampFormatter::new(DisplayTimezone::Original, None);
+
+        (
+            entry.is_match(&highlighter, &timestamps),
+            entry.count_matches(&highlighter, &timestamps),
+        )
+    }
 }
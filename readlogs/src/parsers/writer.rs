@@ -0,0 +1,143 @@
+//! Writing parsed content back as text in the formats that the parsers accept.
+
+use crate::{
+    parsers::{GenericTable, InfoEntry, Value},
+    timezone,
+};
+
+/// Header of a top-level section, e.g. `========= LOGGER =========`.
+pub fn section_header(name: &str) -> String {
+    format!("========= {name} =========\n")
+}
+
+/// The value as the parsers accept it, with buckets written as `country code:value` pairs.
+pub fn value(value: &Value) -> String {
+    match value {
+        Value::Generic(value) => value.clone(),
+        Value::BucketedFlag(buckets) => buckets
+            .iter()
+            .map(|bucket| format!("{}:{}", bucket.country_code, bucket.value))
+            .collect::<Vec<_>>()
+            .join(","),
+    }
+}
+
+/// The key-value pair as `key: value`, or `key: enabled value`, if the entry is one.
+pub fn key_value(entry: &InfoEntry) -> Option<String> {
+    match entry {
+        InfoEntry::KeyValue(key, value) => Some(format!("{key}: {}", self::value(value))),
+        InfoEntry::KeyEnabledValue(key, enabled, value) => {
+            let enabled = if *enabled { "enabled" } else { "disabled" };
+
+            Some(match value {
+                Some(value) => format!("{key}: {enabled} {}", self::value(value)),
+                None => format!("{key}: {enabled}"),
+            })
+        }
+        _ => None,
+    }
+}
+
+/// The table with pipe-delimited cells and a separator line after the header.
+pub fn table(table: &GenericTable) -> String {
+    let row = |cells: &[String]| {
+        cells
+            .iter()
+            .map(|cell| format!("| {cell} "))
+            .collect::<String>()
+            + "|\n"
+    };
+
+    let separator = table
+        .header
+        .iter()
+        .map(|cell| format!("|{}", "-".repeat(cell.len() + 2)))
+        .collect::<String>()
+        + "|\n";
+
+    row(&table.header)
+        + &separator
+        + &table
+            .rows
+            .iter()
+            .map(|cells| row(cells))
+            .collect::<String>()
+}
+
+/// Lines of the entry: a key-value pair, a table or text.
+pub fn info_entry(entry: &InfoEntry) -> String {
+    match entry {
+        InfoEntry::KeyValue(..) | InfoEntry::KeyEnabledValue(..) => {
+            key_value(entry).unwrap_or_default() + "\n"
+        }
+        InfoEntry::RemoteObject(ro) => ro.debuglogs_url() + "\n",
+        InfoEntry::ExplicitNone => "None\n".to_owned(),
+        InfoEntry::GenericTable(table) => self::table(table),
+        InfoEntry::Generic(text) => text.clone() + "\n",
+    }
+}
+
+/// Date and time of a timestamp as formatted by the parsers, reformatted with the format string,
+/// along with its time zone. Timestamps that can't be parsed are returned as they are.
+pub fn reformat_timestamp<'a>(timestamp: &'a str, format: &str) -> (String, &'a str) {
+    match timezone::split_timestamp(timestamp) {
+        Some((date_time, time_zone)) => (date_time.format(format).to_string(), time_zone),
+        None => (timestamp.to_owned(), ""),
+    }
+}
+
+/// The message's lines, each of which is preceded by the metadata.
+///
+/// Some formats repeat the metadata of an entry on each line of its message, which the parsers
+/// collapse into one entry.
+pub fn lines_with_metadata(metadata: &str, message: &str) -> String {
+    message
+        .split('\n')
+        .map(|line| format!("{metadata}{line}\n"))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+
+    use super::*;
+    use crate::parsers::common::test_bucket;
+
+    #[test_case(InfoEntry::KeyValue("a".to_owned(), Value::Generic("b".to_owned())) => Some("a: b".to_owned()); "key-value")]
+    #[test_case(InfoEntry::KeyEnabledValue("a".to_owned(), false, None) => Some("a: disabled".to_owned()); "disabled")]
+    #[test_case(
+        InfoEntry::KeyEnabledValue("a".to_owned(), true, Some(Value::BucketedFlag(vec![test_bucket("1", 2), test_bucket("*", 3)])))
+        => Some("a: enabled 1:2,*:3".to_owned());
+        "enabled bucketed"
+    )]
+    #[test_case(InfoEntry::ExplicitNone => None; "not a key-value pair")]
+    fn key_value_ok(entry: InfoEntry) -> Option<String> {
+        key_value(&entry)
+    }
+
+    #[test]
+    fn table_ok() {
+        let table = GenericTable {
+            header: vec!["_id".to_owned(), "name".to_owned()],
+            rows: vec![vec!["1".to_owned(), "abc".to_owned()]],
+        };
+
+        assert_eq!(
+            super::table(&table),
+            "| _id | name |\n|-----|------|\n| 1 | abc |\n"
+        );
+    }
+
+    #[test_case(InfoEntry::ExplicitNone => "None\n"; "explicit none")]
+    #[test_case(InfoEntry::Generic("Some text".to_owned()) => "Some text\n"; "generic")]
+    fn info_entry_ok(entry: InfoEntry) -> String {
+        info_entry(&entry)
+    }
+
+    #[test_case("1234-01-23 12:34:56.789 +01:00" => ("01-23 12:34:56.789".to_owned(), "+01:00"); "parsed")]
+    #[test_case("12:34" => ("12:34".to_owned(), ""); "unparsed")]
+    fn reformat_timestamp_ok(timestamp: &str) -> (String, &str) {
+        reformat_timestamp(timestamp, "%m-%d %H:%M:%S%.3f")
+    }
+}
@@ -0,0 +1,291 @@
+use std::{
+    collections::HashMap,
+    hash::{Hash, Hasher},
+    rc::Rc,
+};
+
+use regex::{Captures, Regex};
+
+use crate::{
+    parsers::{LogEntry, Section},
+    LogLevel,
+};
+
+const UUID_PLACEHOLDER: &str = "<uuid>";
+const TIMESTAMP_PLACEHOLDER: &str = "<timestamp>";
+const HEX_PLACEHOLDER: &str = "<hex>";
+const NUMBER_PLACEHOLDER: &str = "<num>";
+
+const UUID_PATTERN: &str =
+    r"[0-9a-fA-F*]{8}-[0-9a-fA-F*]{4}-[0-9a-fA-F*]{4}-[0-9a-fA-F*]{4}-[0-9a-fA-F*]{12}";
+const TIMESTAMP_PATTERN: &str =
+    r"\d{4}[-/]\d{2}[-/]\d{2}[T ]\d{2}:\d{2}:\d{2}(?:[.:]\d+)?Z?|\d{2}:\d{2}:\d{2}(?:\.\d+)?";
+const HEX_PATTERN: &str = r"\b(?:0x)?[0-9a-fA-F]{8,}\b";
+const NUMBER_PATTERN: &str = r"-?\d+(?:\.\d+)?";
+
+/// Replaces parts of log messages that vary between otherwise identical messages
+/// (UUIDs, timestamps, hex ids and numbers) with placeholders.
+#[derive(Debug)]
+pub struct Templater {
+    regex: Regex,
+}
+
+impl Default for Templater {
+    fn default() -> Self {
+        Self {
+            regex: Regex::new(&format!(
+                "(?P<uuid>{UUID_PATTERN})|(?P<timestamp>{TIMESTAMP_PATTERN})|(?P<hex>{HEX_PATTERN})|(?P<num>{NUMBER_PATTERN})"
+            ))
+            .unwrap(),
+        }
+    }
+}
+
+impl Templater {
+    pub fn template(&self, message: &str) -> String {
+        self.regex
+            .replace_all(message, |captures: &Captures| {
+                if captures.name("uuid").is_some() {
+                    UUID_PLACEHOLDER
+                } else if captures.name("timestamp").is_some() {
+                    TIMESTAMP_PLACEHOLDER
+                } else if captures
+                    .name("hex")
+                    .filter(|hex| !hex.as_str().bytes().all(|b| b.is_ascii_digit()))
+                    .is_some()
+                {
+                    HEX_PLACEHOLDER
+                } else {
+                    NUMBER_PLACEHOLDER
+                }
+            })
+            .into_owned()
+    }
+
+    /// Regular expression that matches all messages that have the given template.
+    pub fn template_regex(template: &str) -> String {
+        let regex = regex::escape(template)
+            .replace(UUID_PLACEHOLDER, &format!("(?:{UUID_PATTERN})"))
+            .replace(TIMESTAMP_PLACEHOLDER, &format!("(?:{TIMESTAMP_PATTERN})"))
+            .replace(HEX_PLACEHOLDER, &format!("(?:{HEX_PATTERN})"))
+            .replace(NUMBER_PLACEHOLDER, &format!("(?:{NUMBER_PATTERN})"));
+
+        format!("^{regex}$")
+    }
+}
+
+/// Template that log messages are filtered by, with its regular expression compiled once.
+#[derive(Debug, Clone)]
+pub struct MessagePattern {
+    pub template: String,
+    regex: Rc<Regex>,
+}
+
+impl MessagePattern {
+    pub fn new(template: String) -> Self {
+        let regex = Regex::new(&Templater::template_regex(&template)).unwrap();
+
+        Self {
+            template,
+            regex: Rc::new(regex),
+        }
+    }
+
+    /// Whether the message, as it was logged, has the template.
+    pub fn is_match(&self, message: &str) -> bool {
+        self.regex.is_match(message)
+    }
+}
+
+impl PartialEq for MessagePattern {
+    fn eq(&self, other: &Self) -> bool {
+        self.template == other.template
+    }
+}
+
+impl Eq for MessagePattern {}
+
+impl Hash for MessagePattern {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.template.hash(state);
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Occurrence {
+    /// Position of the entry among all entries of all sections, used for ordering.
+    pub index: usize,
+    pub timestamp: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pattern {
+    pub template: String,
+    pub tag: Option<String>,
+    pub count: usize,
+    pub max_level: Option<LogLevel>,
+    pub first: Occurrence,
+    pub last: Occurrence,
+}
+
+impl Pattern {
+    fn merge(&mut self, other: &Pattern) {
+        self.count += other.count;
+        self.max_level = self.max_level.max(other.max_level);
+
+        if other.first.index < self.first.index {
+            self.first = other.first.clone();
+        }
+
+        if other.last.index > self.last.index {
+            self.last = other.last.clone();
+        }
+    }
+}
+
+/// Groups the entries of `sections` (and their subsections) by template and tag,
+/// sorted by count in descending order.
+pub fn cluster_log_entries(sections: &[Section<LogEntry>]) -> Vec<Pattern> {
+    fn visit<'a>(section: &'a Section<LogEntry>, entries: &mut Vec<&'a LogEntry>) {
+        entries.extend(section.content.iter());
+        section
+            .subsections
+            .iter()
+            .for_each(|subsection| visit(subsection, entries));
+    }
+
+    let mut entries = vec![];
+    sections
+        .iter()
+        .for_each(|section| visit(section, &mut entries));
+
+    let templater = Templater::default();
+    let mut patterns: HashMap<(String, Option<String>), Pattern> = HashMap::new();
+
+    for (index, entry) in entries.into_iter().enumerate() {
+        let template = templater.template(&entry.message);
+        let tag = entry.meta.tag().map(ToOwned::to_owned);
+        let occurrence = Occurrence {
+            index,
+            timestamp: entry.timestamp.clone(),
+        };
+
+        let pattern = Pattern {
+            template: template.clone(),
+            tag: tag.clone(),
+            count: 1,
+            max_level: entry.level,
+            first: occurrence.clone(),
+            last: occurrence,
+        };
+
+        patterns
+            .entry((template, tag))
+            .and_modify(|existing| existing.merge(&pattern))
+            .or_insert(pattern);
+    }
+
+    sorted(patterns.into_values().collect())
+}
+
+/// Merges patterns that only differ by tag.
+pub fn ignore_tags(patterns: &[Pattern]) -> Vec<Pattern> {
+    let mut merged: HashMap<&str, Pattern> = HashMap::new();
+
+    for pattern in patterns {
+        merged
+            .entry(&pattern.template)
+            .and_modify(|existing| existing.merge(pattern))
+            .or_insert_with(|| Pattern {
+                tag: None,
+                ..pattern.clone()
+            });
+    }
+
+    sorted(merged.into_values().collect())
+}
+
+fn sorted(mut patterns: Vec<Pattern>) -> Vec<Pattern> {
+    patterns.sort_by(|a, b| {
+        b.count
+            .cmp(&a.count)
+            .then_with(|| a.first.index.cmp(&b.first.index))
+    });
+    patterns
+}
+
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+
+    use super::*;
+    use crate::parsers::test_entry;
+
+    #[test_case("Sending message 1675000000000 to 12" => "Sending message <num> to <num>"; "numbers")]
+    #[test_case("Took 1.5 ms" => "Took <num> ms"; "decimal")]
+    #[test_case("JOB::abcd1234-ef12-5678-abcd-9012abcd1234 done" => "JOB::<uuid> done"; "uuid")]
+    #[test_case("Recipient ********-****-****-****-**********12" => "Recipient <uuid>"; "redacted uuid")]
+    #[test_case("Hash 0x1f2e3d4c5b6a, id deadbeef01" => "Hash <hex>, id <hex>"; "hex")]
+    #[test_case("Scheduled at 2023-01-22 12:34:56.789" => "Scheduled at <timestamp>"; "timestamp")]
+    #[test_case("No variable parts" => "No variable parts"; "unchanged")]
+    fn template(message: &str) -> String {
+        Templater::default().template(message)
+    }
+
+    #[test_case("Sending message 1675000000000 to 12")]
+    #[test_case("JOB::abcd1234-ef12-5678-abcd-9012abcd1234 (done?)")]
+    #[test_case("Scheduled at 2023-01-22 12:34:56.789, hash deadbeef01")]
+    fn template_regex_matches_original(message: &str) {
+        let templater = Templater::default();
+        let regex = Regex::new(&Templater::template_regex(&templater.template(message))).unwrap();
+
+        assert!(regex.is_match(message));
+    }
+
+    #[test_case("Response {\"id\":12,\"ok\":true} in 15 ms" => true; "same message")]
+    #[test_case("Response {\"id\":345,\"ok\":true} in 2 ms" => true; "other numbers")]
+    #[test_case("Response {\"id\":12,\"ok\":false} in 15 ms" => false; "other json")]
+    #[test_case("Response {\"id\":12,\"ok\":true} in 15 ms\n\tat a.B.c(B.java:1)" => false; "longer")]
+    fn message_pattern_of_json(message: &str) -> bool {
+        let template = Templater::default().template("Response {\"id\":12,\"ok\":true} in 15 ms");
+
+        MessagePattern::new(template).is_match(message)
+    }
+
+    #[test]
+    fn cluster_log_entries_ok() {
+        let sections = vec![Section {
+            name: "LOGGER".to_owned(),
+            content: vec![
+                test_entry("1", "1", "main", "A", "Fetched 1 messages"),
+                test_entry("2", "1", "main", "B", "Something else"),
+                test_entry("3", "1", "main", "A", "Fetched 20 messages"),
+                test_entry("4", "1", "main", "C", "Fetched 300 messages"),
+            ],
+            subsections: vec![],
+        }];
+
+        let patterns = cluster_log_entries(&sections);
+
+        assert_eq!(
+            patterns
+                .iter()
+                .map(|p| (p.template.as_str(), p.tag.as_deref(), p.count))
+                .collect::<Vec<_>>(),
+            vec![
+                ("Fetched <num> messages", Some("A"), 2),
+                ("Something else", Some("B"), 1),
+                ("Fetched <num> messages", Some("C"), 1),
+            ]
+        );
+
+        let merged = ignore_tags(&patterns);
+
+        assert_eq!(merged.len(), 2);
+        assert_eq!(merged[0].template, "Fetched <num> messages");
+        assert_eq!(merged[0].tag, None);
+        assert_eq!(merged[0].count, 3);
+        assert_eq!(merged[0].first.timestamp, "1");
+        assert_eq!(merged[0].last.timestamp, "4");
+    }
+}
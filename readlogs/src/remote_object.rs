@@ -1,3 +1,6 @@
+use std::{fmt, str::FromStr, sync::OnceLock};
+
+use anyhow::ensure;
 use nom::{
     branch::alt,
     bytes::complete::{is_a, is_not, tag},
@@ -5,6 +8,7 @@ use nom::{
     sequence::{preceded, separated_pair, terminated, tuple},
     IResult,
 };
+use regex::Regex;
 
 use crate::{
     impl_from_str,
@@ -15,6 +19,67 @@ use crate::{
 pub const KEY_LENGTH: usize = 64;
 pub const BASE_DEBUGLOGS_URL: &str = "https://debuglogs.org/";
 pub const BASE_WORKER_URL: &str = "https://getlogs.warp.workers.dev/";
+/// Value of the `proxy` query parameter (and setting) that makes the web app fetch directly.
+pub const DIRECT_FETCH_MODE: &str = "direct";
+
+/// How debug logs are fetched.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FetchMode {
+    /// Through a proxy (e.g. the worker in this repository) with the given base URL,
+    /// which makes the response available to the web app.
+    Proxy(String),
+    /// Straight from debuglogs.org, if the browser allows it (e.g. due to an extension).
+    Direct,
+}
+
+impl Default for FetchMode {
+    fn default() -> Self {
+        Self::Proxy(BASE_WORKER_URL.to_owned())
+    }
+}
+
+impl FromStr for FetchMode {
+    type Err = anyhow::Error;
+
+    /// Parses either `direct` or the base URL of a proxy.
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let input = input.trim();
+
+        if input.eq_ignore_ascii_case(DIRECT_FETCH_MODE) {
+            return Ok(Self::Direct);
+        }
+
+        ensure!(
+            input.starts_with("https://") || input.starts_with("http://"),
+            "the proxy URL should start with `https://` or `http://`"
+        );
+
+        Ok(Self::Proxy(if input.ends_with('/') {
+            input.to_owned()
+        } else {
+            format!("{input}/")
+        }))
+    }
+}
+
+impl fmt::Display for FetchMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FetchMode::Proxy(base) => write!(f, "{base}"),
+            FetchMode::Direct => write!(f, "{DIRECT_FETCH_MODE}"),
+        }
+    }
+}
+
+impl FetchMode {
+    /// URL that responds successfully if fetching debug logs should work.
+    pub fn health_check_url(&self) -> String {
+        match self {
+            FetchMode::Proxy(base) => format!("{base}health"),
+            FetchMode::Direct => BASE_DEBUGLOGS_URL.to_owned(),
+        }
+    }
+}
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct RemoteObject {
@@ -63,10 +128,16 @@ impl RemoteObject {
         }
     }
 
-    pub fn fetchable_url(&self) -> String {
+    /// URL that the debug log can be fetched from by the web app.
+    pub fn fetchable_url(&self, mode: &FetchMode) -> String {
+        let base = match mode {
+            FetchMode::Proxy(base) => base,
+            FetchMode::Direct => return self.debuglogs_url(),
+        };
+
         format!(
             "{}{}/{}{}",
-            BASE_WORKER_URL,
+            base,
             self.platform.to_string().to_lowercase(),
             self.key,
             match &self.version {
@@ -132,6 +203,49 @@ pub fn remote_object(input: Span) -> IResult<Span, RemoteObject> {
 
 impl_from_str!(remote_object => RemoteObject);
 
+fn url_regex() -> &'static Regex {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+
+    // Wrapped URLs are split by line breaks (or spaces, once pasted into a single-line input),
+    // which are allowed between the parts of the URL and inside the key. The host can't be
+    // preceded by anything that would make it part of another one, e.g. `fakedebuglogs.org`.
+    REGEX.get_or_init(|| {
+        Regex::new(
+            r"(?:^|[^\w.-])(?P<url>debuglogs\.org/\s*(?:(?:android|ios|desktop)/\s*[^/\s]+/\s*)?[0-9a-f](?:\s*[0-9a-f]){63}(?:\s*(?:\.zip|\.gz))?)",
+        )
+        .unwrap()
+    })
+}
+
+/// Every distinct debuglogs.org URL in the free text (e.g. a pasted email), in order.
+///
+/// URLs may be missing the scheme, be surrounded by punctuation or be wrapped across lines.
+pub fn find_remote_objects(text: &str) -> Vec<RemoteObject> {
+    let text = text.to_lowercase();
+
+    let mut remote_objects: Vec<RemoteObject> = vec![];
+
+    for captures in url_regex().captures_iter(&text) {
+        let url = format!(
+            "https://{}",
+            captures["url"].split_whitespace().collect::<String>()
+        );
+
+        let Ok(remote_object) = url.parse::<RemoteObject>() else {
+            continue;
+        };
+
+        if !remote_objects
+            .iter()
+            .any(|existing| existing.key == remote_object.key)
+        {
+            remote_objects.push(remote_object);
+        }
+    }
+
+    remote_objects
+}
+
 #[cfg(test)]
 mod tests {
     use test_case::test_case;
@@ -279,7 +393,7 @@ mod tests {
         "desktop"
     )]
     fn fetchable_url_old(input: RemoteObject) -> String {
-        input.fetchable_url()
+        input.fetchable_url(&FetchMode::default())
     }
 
     #[test_case(
@@ -298,6 +412,99 @@ mod tests {
         "desktop"
     )]
     fn fetchable_url_new(input: RemoteObject) -> String {
-        input.fetchable_url()
+        input.fetchable_url(&FetchMode::default())
+    }
+
+    #[test_case(
+        FetchMode::Proxy("http://127.0.0.1:8787/".to_owned()) =>
+        "http://127.0.0.1:8787/ios/0123456789abcdefghij0123456789abcdefghij0123456789abcdefghij0123?v=123".to_owned();
+        "proxy"
+    )]
+    #[test_case(
+        FetchMode::Direct =>
+        "https://debuglogs.org/ios/123/0123456789abcdefghij0123456789abcdefghij0123456789abcdefghij0123.zip".to_owned();
+        "direct"
+    )]
+    fn fetchable_url_mode(mode: FetchMode) -> String {
+        RemoteObject::new_unchecked(
+            Platform::Ios,
+            Some("123".to_owned()),
+            "0123456789abcdefghij0123456789abcdefghij0123456789abcdefghij0123",
+        )
+        .fetchable_url(&mode)
+    }
+
+    #[test_case("direct" => FetchMode::Direct; "direct")]
+    #[test_case(" https://proxy.example.com/logs " => FetchMode::Proxy("https://proxy.example.com/logs/".to_owned()); "proxy without trailing slash")]
+    #[test_case("http://127.0.0.1:8787/" => FetchMode::Proxy("http://127.0.0.1:8787/".to_owned()); "proxy")]
+    fn fetch_mode_ok(input: &str) -> FetchMode {
+        input.parse().unwrap()
+    }
+
+    #[test_case(""; "empty")]
+    #[test_case("proxy.example.com"; "no scheme")]
+    fn fetch_mode_err(input: &str) {
+        assert!(input.parse::<FetchMode>().is_err());
+    }
+
+    #[test_case(FetchMode::Proxy("http://127.0.0.1:8787/".to_owned()) => "http://127.0.0.1:8787/health".to_owned(); "proxy")]
+    #[test_case(FetchMode::Direct => "https://debuglogs.org/".to_owned(); "direct")]
+    fn health_check_url_ok(mode: FetchMode) -> String {
+        mode.health_check_url()
+    }
+
+    const KEY_A: &str = "0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef";
+    const KEY_B: &str = "fedcba9876543210fedcba9876543210fedcba9876543210fedcba9876543210";
+
+    #[test]
+    fn find_remote_objects_email() {
+        let text = format!(
+            "Hi,\n\nhere are the logs: <https://debuglogs.org/ios/6.1.0/{KEY_A}.zip>, and from\nthe desktop app (debuglogs.org/{KEY_B}.gz).\n\nThanks!"
+        );
+
+        assert_eq!(
+            find_remote_objects(&text),
+            vec![
+                RemoteObject::new_unchecked(Platform::Ios, Some("6.1.0".to_owned()), KEY_A),
+                RemoteObject::new_unchecked(Platform::Desktop, None, KEY_B),
+            ]
+        );
+    }
+
+    #[test]
+    fn find_remote_objects_wrapped() {
+        let text = format!(
+            "See HTTPS://DEBUGLOGS.ORG/android/6.10.0/{}\n    {}\nand again https://debuglogs.org/android/6.10.0/{KEY_A}.",
+            &KEY_A[..30],
+            &KEY_A[30..],
+        );
+
+        assert_eq!(
+            find_remote_objects(&text),
+            vec![RemoteObject::new_unchecked(
+                Platform::Android,
+                Some("6.10.0".to_owned()),
+                KEY_A
+            )]
+        );
+    }
+
+    #[test]
+    fn find_remote_objects_wrapped_with_space() {
+        let text = format!("debuglogs.org/{} {} thanks", &KEY_B[..40], &KEY_B[40..],);
+
+        assert_eq!(
+            find_remote_objects(&text),
+            vec![RemoteObject::new_unchecked(Platform::Android, None, KEY_B)]
+        );
+    }
+
+    #[test_case("no links here"; "none")]
+    #[test_case("https://debuglogs.org/0123"; "short key")]
+    #[test_case("https://example.com/0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef"; "other host")]
+    #[test_case("https://fakedebuglogs.org/0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef"; "host with a prefix")]
+    #[test_case("see logs.debuglogs.org/0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef"; "subdomain")]
+    fn find_remote_objects_none(text: &str) {
+        assert_eq!(find_remote_objects(text), vec![]);
     }
 }
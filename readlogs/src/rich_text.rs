@@ -0,0 +1,444 @@
+use std::sync::OnceLock;
+
+use regex::Regex;
+use serde_json::Value;
+use wasm_bindgen::JsCast;
+use web_sys::Element;
+use yew::prelude::*;
+
+use crate::Highlighter;
+
+/// Number of stack trace frames that are shown before the rest are folded.
+const VISIBLE_FRAMES: usize = 3;
+/// Number of `{` or `[` in a message that are tried as the start of JSON, to keep long messages fast.
+const MAX_JSON_CANDIDATES: usize = 16;
+
+/// Part of a log message that's displayed in its own way.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Block {
+    Text(String),
+    /// Pretty-printed JSON (which is an object or an array).
+    Json(String),
+    /// Consecutive lines of a stack trace, e.g. `at com.example.Class.method(Class.java:1)`.
+    Frames(Vec<String>),
+}
+
+/// Attribute of tokens that the logs can be filtered by, with the token as its value.
+const PIVOT_ATTRIBUTE: &str = "data-pivot";
+
+/// Kind of a token that stands out in text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenKind {
+    Uuid,
+    /// E.g. `JOB::0123abcd-...`.
+    JobId,
+    /// E.g. `RecipientId::42`.
+    RecipientId,
+    /// Milliseconds since the Unix epoch, which messages are identified by.
+    Timestamp,
+    Duration,
+    HttpStatus,
+    /// Something that the app replaced with `*` characters or `[REDACTED]` before submitting.
+    Redacted,
+}
+
+impl TokenKind {
+    /// Whether the logs can be filtered by the token, as it identifies something.
+    pub fn is_pivot(&self) -> bool {
+        matches!(
+            self,
+            TokenKind::Uuid | TokenKind::JobId | TokenKind::RecipientId | TokenKind::Timestamp
+        )
+    }
+
+    fn classes(&self) -> Classes {
+        match self {
+            TokenKind::Uuid | TokenKind::JobId | TokenKind::RecipientId | TokenKind::Timestamp => {
+                classes!("text-violet-600", "dark:text-violet-400")
+            }
+            TokenKind::Duration => classes!("text-teal-600", "dark:text-teal-400"),
+            TokenKind::HttpStatus => classes!("font-bold"),
+            TokenKind::Redacted => classes!("text-gray-400", "dark:text-gray-500", "italic"),
+        }
+    }
+}
+
+fn is_frame(line: &str) -> bool {
+    let line = line.trim_start();
+    line.starts_with("at ") || (line.starts_with("... ") && line.ends_with(" more"))
+}
+
+/// Splits the message into text, JSON and stack trace frames, in the order they're in.
+pub fn blocks(message: &str) -> Vec<Block> {
+    let mut blocks = vec![];
+    let mut text_lines = vec![];
+    let mut frames = vec![];
+
+    for line in message.split('\n') {
+        if is_frame(line) {
+            if !text_lines.is_empty() {
+                split_json(&text_lines.join("\n"), &mut blocks);
+                text_lines.clear();
+            }
+            frames.push(line.to_owned());
+        } else {
+            if !frames.is_empty() {
+                blocks.push(Block::Frames(frames));
+                frames = vec![];
+            }
+            text_lines.push(line);
+        }
+    }
+
+    if !text_lines.is_empty() {
+        split_json(&text_lines.join("\n"), &mut blocks);
+    }
+    if !frames.is_empty() {
+        blocks.push(Block::Frames(frames));
+    }
+
+    blocks
+}
+
+/// Adds the text to the blocks, with any JSON objects and arrays in it as separate blocks.
+fn split_json(text: &str, blocks: &mut Vec<Block>) {
+    let mut rest = text;
+    let mut search_from = 0;
+
+    for _ in 0..MAX_JSON_CANDIDATES {
+        let Some(offset) = rest[search_from..].find(['{', '[']) else {
+            break;
+        };
+        let start = search_from + offset;
+
+        let mut values = serde_json::Deserializer::from_str(&rest[start..]).into_iter::<Value>();
+
+        match values.next() {
+            Some(Ok(value)) if is_structured(&value) => {
+                let end = start + values.byte_offset();
+
+                if start > 0 {
+                    blocks.push(Block::Text(rest[..start].to_owned()));
+                }
+                blocks.push(Block::Json(indent_json(&rest[start..end])));
+
+                rest = &rest[end..];
+                search_from = 0;
+            }
+            _ => search_from = start + 1,
+        }
+    }
+
+    if !rest.is_empty() {
+        blocks.push(Block::Text(rest.to_owned()));
+    }
+}
+
+/// Whether the JSON is worth pretty-printing: `[1]` in a message is most likely not JSON.
+fn is_structured(value: &Value) -> bool {
+    match value {
+        Value::Object(object) => !object.is_empty(),
+        Value::Array(items) => items.iter().any(|item| item.is_object() || item.is_array()),
+        _ => false,
+    }
+}
+
+/// Pretty-prints valid JSON, keeping the order of object keys (unlike `serde_json::Value`).
+fn indent_json(json: &str) -> String {
+    fn new_line(output: &mut String, depth: usize) {
+        output.push('\n');
+        output.push_str(&"  ".repeat(depth));
+    }
+
+    let mut output = String::new();
+    let mut depth = 0;
+    let mut in_string = false;
+    let mut escaped = false;
+    let mut chars = json.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if in_string {
+            output.push(c);
+
+            if escaped {
+                escaped = false;
+            } else if c == '\\' {
+                escaped = true;
+            } else if c == '"' {
+                in_string = false;
+            }
+
+            continue;
+        }
+
+        match c {
+            '"' => {
+                in_string = true;
+                output.push(c);
+            }
+            '{' | '[' => {
+                output.push(c);
+
+                while matches!(chars.peek(), Some(c) if c.is_whitespace()) {
+                    chars.next();
+                }
+
+                // Empty objects and arrays stay on one line.
+                if matches!(chars.peek(), Some('}' | ']')) {
+                    output.push(chars.next().unwrap());
+                } else {
+                    depth += 1;
+                    new_line(&mut output, depth);
+                }
+            }
+            '}' | ']' => {
+                depth = depth.saturating_sub(1);
+                new_line(&mut output, depth);
+                output.push(c);
+            }
+            ',' => {
+                output.push(c);
+                new_line(&mut output, depth);
+            }
+            ':' => output.push_str(": "),
+            c if c.is_whitespace() => {}
+            c => output.push(c),
+        }
+    }
+
+    output
+}
+
+fn token_regex() -> &'static Regex {
+    static REGEX: OnceLock<Regex> = OnceLock::new();
+
+    REGEX.get_or_init(|| {
+        Regex::new(concat!(
+            r"(?P<job>\bJOB::[0-9A-Za-z*-]+)",
+            r"|(?P<recipient>\bRecipientId::\d+)",
+            r"|(?P<uuid>\b[0-9a-fA-F]{8}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{4}-[0-9a-fA-F]{12}\b)",
+            r"|(?P<timestamp>\b1\d{12}\b)",
+            r"|(?P<redacted>\[REDACTED\]|[^\s*]*\*{3,}[^\s*,;)\]]*)",
+            r"|(?P<status>(?i:\b(?:status(?: code)?|http(?:/\d(?:\.\d)?)?|response code|code)[ :=]+[1-5]\d\d\b))",
+            r"|(?P<duration>\b\d+(?:\.\d+)?\s?(?:ns|µs|us|ms|s|sec|secs|seconds|min|mins|minutes|h|hours)\b)",
+        ))
+        .unwrap()
+    })
+}
+
+/// Splits the text into tokens that stand out and the text between them.
+pub fn tokens(text: &str) -> Vec<(Option<TokenKind>, &str)> {
+    let mut parts = vec![];
+    let mut last_end = 0;
+
+    for captures in token_regex().captures_iter(text) {
+        let (kind, m) = [
+            ("job", TokenKind::JobId),
+            ("recipient", TokenKind::RecipientId),
+            ("uuid", TokenKind::Uuid),
+            ("timestamp", TokenKind::Timestamp),
+            ("redacted", TokenKind::Redacted),
+            ("status", TokenKind::HttpStatus),
+            ("duration", TokenKind::Duration),
+        ]
+        .into_iter()
+        .find_map(|(name, kind)| captures.name(name).map(|m| (kind, m)))
+        .unwrap();
+
+        if m.start() > last_end {
+            parts.push((None, &text[last_end..m.start()]));
+        }
+        parts.push((Some(kind), m.as_str()));
+        last_end = m.end();
+    }
+
+    if last_end < text.len() {
+        parts.push((None, &text[last_end..]));
+    }
+
+    parts
+}
+
+fn view_tokens(text: &str) -> Html {
+    html! {
+        for tokens(text).into_iter().map(|(kind, text)| match kind {
+            Some(kind) if kind.is_pivot() => html! {
+                <span
+                    class={classes!(kind.classes(), "cursor-pointer", "underline", "decoration-dotted")}
+                    data-pivot={text.to_owned()}
+                    title={format!("Show only entries mentioning {text}")}
+                >
+                    { text }
+                </span>
+            },
+            Some(kind) => html! { <span class={kind.classes()}>{ text }</span> },
+            None => html! { text },
+        })
+    }
+}
+
+/// Identifier that was clicked, if a token that the logs can be filtered by was.
+pub fn pivot_target(event: &MouseEvent) -> Option<String> {
+    event
+        .target()?
+        .dyn_into::<Element>()
+        .ok()?
+        .closest(&format!("[{PIVOT_ATTRIBUTE}]"))
+        .ok()??
+        .get_attribute(PIVOT_ATTRIBUTE)
+}
+
+/// Whether the click was inside folded stack trace frames, which it toggles.
+pub fn is_in_fold(event: &MouseEvent) -> bool {
+    event
+        .target()
+        .and_then(|target| target.dyn_into::<Element>().ok())
+        .and_then(|element| element.closest("details").ok().flatten())
+        .is_some()
+}
+
+/// Number of matches that [`view`] highlights in the message.
+pub fn count_matches(message: &str, highlighter: &Highlighter) -> usize {
+    if !highlighter.is_active() {
+        return 0;
+    }
+
+    blocks(message)
+        .iter()
+        .map(|block| match block {
+            Block::Text(text) | Block::Json(text) => highlighter.count(text),
+            Block::Frames(frames) => frames.iter().map(|frame| highlighter.count(frame)).sum(),
+        })
+        .sum()
+}
+
+/// Displays the message with JSON pretty-printed, long stack traces folded, and tokens styled.
+/// Its matches are numbered from `first_match` (in the order [`count_matches`] counts them) if
+/// they can be navigated between. Meant to be put in a `<pre>`.
+pub fn view(message: &str, highlighter: &Highlighter, first_match: Option<usize>) -> Html {
+    // Numbers the matches in the text from `next_match`, which is moved past them.
+    let view_text = |text: &str, next_match: &mut Option<usize>| {
+        let html = highlighter.view_with(text, *next_match, view_tokens);
+        *next_match = next_match.map(|next_match| next_match + highlighter.count(text));
+        html
+    };
+
+    let mut next_match = first_match;
+    let mut parts = vec![];
+
+    for block in blocks(message) {
+        parts.push(match block {
+            Block::Text(text) => view_text(&text, &mut next_match),
+            Block::Json(json) => html! {
+                <div class="my-1 pl-2 border-l-2 border-brand-border dark:border-brand-dark-border">
+                    { view_text(&json, &mut next_match) }
+                </div>
+            },
+            Block::Frames(frames) => {
+                let (visible, hidden) = frames.split_at(frames.len().min(VISIBLE_FRAMES));
+
+                let visible = html! {
+                    { for visible.iter().map(|frame| html! { <div>{ view_text(frame, &mut next_match) }</div> }) }
+                };
+
+                let folded = if hidden.is_empty() {
+                    html! {}
+                } else {
+                    // The current match is scrolled to, which it can't be while it's folded.
+                    let open = matches!(next_match, Some(next_match) if highlighter.is_current_among(
+                        next_match,
+                        hidden.iter().map(|frame| highlighter.count(frame)).sum(),
+                    ));
+
+                    html! {
+                        <details {open}>
+                            <summary class="cursor-pointer font-sans italic">
+                                { format!("{} more frames", hidden.len()) }
+                            </summary>
+                            { for hidden.iter().map(|frame| html! { <div>{ view_text(frame, &mut next_match) }</div> }) }
+                        </details>
+                    }
+                };
+
+                html! {
+                    <div class="opacity-75">
+                        { visible }
+                        { folded }
+                    </div>
+                }
+            }
+        });
+    }
+
+    html! { for parts }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+
+    use super::*;
+    use crate::SearchQuery;
+
+    fn text(text: &str) -> Block {
+        Block::Text(text.to_owned())
+    }
+
+    #[test_case("Plain message" => vec![text("Plain message")]; "plain")]
+    #[test_case(
+        "Response: {\"a\":1,\"b\":[2,3]} done"
+        => vec![text("Response: "), Block::Json("{\n  \"a\": 1,\n  \"b\": [\n    2,\n    3\n  ]\n}".to_owned()), text(" done")];
+        "json object"
+    )]
+    #[test_case("Keeps {\"z\":1,\"a\":{}} order" => vec![text("Keeps "), Block::Json("{\n  \"z\": 1,\n  \"a\": {}\n}".to_owned()), text(" order")]; "key order")]
+    #[test_case("[main] list [1, 2] {\"unterminated\": " => vec![text("[main] list [1, 2] {\"unterminated\": ")]; "not json")]
+    #[test_case("String with {\"a\":\"}, [x\"}" => vec![text("String with "), Block::Json("{\n  \"a\": \"}, [x\"\n}".to_owned())]; "brackets in strings")]
+    fn blocks_json(message: &str) -> Vec<Block> {
+        blocks(message)
+    }
+
+    #[test]
+    fn blocks_stack_trace() {
+        let message = "java.lang.IllegalStateException: oops\n\tat a.B.c(B.java:1)\n\tat a.B.d(B.java:2)\n\t... 5 more\nCaused by: x";
+
+        assert_eq!(
+            blocks(message),
+            vec![
+                text("java.lang.IllegalStateException: oops"),
+                Block::Frames(vec![
+                    "\tat a.B.c(B.java:1)".to_owned(),
+                    "\tat a.B.d(B.java:2)".to_owned(),
+                    "\t... 5 more".to_owned(),
+                ]),
+                text("Caused by: x"),
+            ]
+        );
+    }
+
+    #[test_case("id 01234567-89ab-cdef-0123-456789abcdef" => vec![(None, "id "), (Some(TokenKind::Uuid), "01234567-89ab-cdef-0123-456789abcdef")]; "uuid")]
+    #[test_case("took 15ms" => vec![(None, "took "), (Some(TokenKind::Duration), "15ms")]; "duration")]
+    #[test_case("took 1.5 s." => vec![(None, "took "), (Some(TokenKind::Duration), "1.5 s"), (None, ".")]; "fractional duration")]
+    #[test_case("Status code: 413" => vec![(Some(TokenKind::HttpStatus), "Status code: 413")]; "status code")]
+    #[test_case("Recipient +*********12, " => vec![(None, "Recipient "), (Some(TokenKind::Redacted), "+*********12"), (None, ", ")]; "redacted phone number")]
+    #[test_case("group [REDACTED]" => vec![(None, "group "), (Some(TokenKind::Redacted), "[REDACTED]")]; "redacted marker")]
+    #[test_case("Job JOB::abcd1234-ef56-7890-abcd-ef1234567890 for RecipientId::42" => vec![(None, "Job "), (Some(TokenKind::JobId), "JOB::abcd1234-ef56-7890-abcd-ef1234567890"), (None, " for "), (Some(TokenKind::RecipientId), "RecipientId::42")]; "job and recipient")]
+    #[test_case("sent 1650000000123," => vec![(None, "sent "), (Some(TokenKind::Timestamp), "1650000000123"), (None, ",")]; "message timestamp")]
+    #[test_case("in 5 minutes or 3 sets" => vec![(None, "in "), (Some(TokenKind::Duration), "5 minutes"), (None, " or 3 sets")]; "not a duration unit")]
+    fn tokens_ok(text: &str) -> Vec<(Option<TokenKind>, &str)> {
+        tokens(text)
+    }
+
+    #[test_case("Sent" => 1; "text")]
+    #[test_case("\"a\": 1" => 1; "pretty-printed json")]
+    #[test_case("\"a\":1,\"b\"" => 0; "raw json")]
+    fn count_matches_in_blocks(string: &str) -> usize {
+        let message = "Sent {\"a\":1,\"b\":2}";
+        let query = SearchQuery {
+            string: string.to_owned(),
+            ..Default::default()
+        };
+        let highlighter = Highlighter::new(&query);
+
+        count_matches(message, &highlighter)
+    }
+}
@@ -0,0 +1,103 @@
+use percent_encoding::{percent_decode_str, percent_encode, NON_ALPHANUMERIC};
+use web_sys::window;
+
+use crate::remote_object::FetchMode;
+
+/// Query parameter that overrides the fetch mode, e.g. `?proxy=http://127.0.0.1:8787` or `?proxy=direct`.
+const PROXY_QUERY_PARAMETER: &str = "proxy";
+/// Query parameter with a debug log URL that's opened right away, e.g. for opening logs in new tabs.
+const LOG_QUERY_PARAMETER: &str = "log";
+/// Local storage key that the fetch mode chosen in the settings is saved under.
+const PROXY_STORAGE_KEY: &str = "readlogs.proxy";
+
+/// Result of requesting `FetchMode::health_check_url`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum HealthCheck {
+    #[default]
+    NotChecked,
+    Checking,
+    Ok,
+    Failed(String),
+}
+
+/// Value of the query parameter in the query string (e.g. `?a=b&c=d`), if it's there.
+fn query_parameter(search: &str, name: &str) -> Option<String> {
+    search
+        .trim_start_matches('?')
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .find(|(key, _)| *key == name)
+        .map(|(_, value)| {
+            percent_decode_str(&value.replace('+', " "))
+                .decode_utf8_lossy()
+                .into_owned()
+        })
+}
+
+fn current_query_parameter(name: &str) -> Option<String> {
+    window()
+        .and_then(|window| window.location().search().ok())
+        .and_then(|search| query_parameter(&search, name))
+}
+
+/// Debug log URL to open right away, if there is one.
+pub fn initial_log_url() -> Option<String> {
+    current_query_parameter(LOG_QUERY_PARAMETER)
+}
+
+/// Relative link to the web app that opens the debug log right away.
+pub fn log_link(url: &str) -> String {
+    format!(
+        "?{LOG_QUERY_PARAMETER}={}",
+        percent_encode(url.as_bytes(), NON_ALPHANUMERIC)
+    )
+}
+
+/// Fetch mode from the query parameter, falling back to the saved one and then the default one.
+pub fn initial_fetch_mode() -> FetchMode {
+    // A valid mode from the query parameter is saved, so that it's used for later visits too.
+    if let Some(mode) =
+        current_query_parameter(PROXY_QUERY_PARAMETER).and_then(|value| value.parse().ok())
+    {
+        save_fetch_mode(&mode);
+        return mode;
+    }
+
+    window()
+        .and_then(|window| window.local_storage().ok().flatten())
+        .and_then(|storage| storage.get_item(PROXY_STORAGE_KEY).ok().flatten())
+        .and_then(|value| value.parse().ok())
+        .unwrap_or_default()
+}
+
+pub fn save_fetch_mode(mode: &FetchMode) {
+    if let Some(storage) = window().and_then(|window| window.local_storage().ok().flatten()) {
+        let _ = if *mode == FetchMode::default() {
+            storage.remove_item(PROXY_STORAGE_KEY)
+        } else {
+            storage.set_item(PROXY_STORAGE_KEY, &mode.to_string())
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+
+    use super::*;
+
+    #[test_case("?proxy=direct" => Some("direct".to_owned()); "plain")]
+    #[test_case("?a=b&proxy=http%3A%2F%2F127.0.0.1%3A8787%2F" => Some("http://127.0.0.1:8787/".to_owned()); "encoded")]
+    #[test_case("?proxyx=direct" => None; "other parameter")]
+    #[test_case("" => None; "empty")]
+    fn query_parameter_ok(search: &str) -> Option<String> {
+        query_parameter(search, "proxy")
+    }
+
+    #[test]
+    fn log_link_round_trip() {
+        let url = "https://debuglogs.org/ios/6.1.0/0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef.zip";
+
+        assert_eq!(query_parameter(&log_link(url), "log"), Some(url.to_owned()));
+    }
+}
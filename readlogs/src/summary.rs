@@ -0,0 +1,292 @@
+use serde::Serialize;
+use yew::prelude::*;
+
+use crate::{
+    components::*,
+    parsers::{Content, InfoEntry, LogEntry, LogFilename, PlatformMetadata, Section, Value},
+    timeline,
+    timezone::TimestampFormatter,
+    RemoteObject,
+};
+
+/// Information keys (in order of preference) that contain the app version.
+const APP_VERSION_KEYS: [&str; 3] = ["App version", "Version", "App"];
+/// Information keys (in order of preference) that contain the OS version,
+/// along with a prefix to add to the value.
+const OS_VERSION_KEYS: [(&str, &str); 4] = [
+    ("OS version", ""),
+    ("OS", ""),
+    ("Android", "Android "),
+    ("Android Version", "Android "),
+];
+const DEVICE_MANUFACTURER_KEY: &str = "Manufacturer";
+const DEVICE_MODEL_KEYS: [&str; 2] = ["Model", "Device"];
+
+/// The most important facts about a debug log, gathered from wherever they are in the file.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct Summary {
+    pub platform: String,
+    pub app: Option<String>,
+    pub app_version: Option<String>,
+    pub os_version: Option<String>,
+    pub device_model: Option<String>,
+    pub first_entry: Option<String>,
+    pub last_entry: Option<String>,
+    pub duration: Option<String>,
+}
+
+impl Summary {
+    pub fn new(
+        remote_object: &RemoteObject,
+        name: Option<&LogFilename>,
+        content: Option<&Content>,
+        timestamps: &TimestampFormatter,
+    ) -> Self {
+        let information = content.map_or(&[][..], |content| &content.information);
+        let logs = content.map_or(&[][..], |content| &content.logs);
+
+        let app_version = APP_VERSION_KEYS
+            .iter()
+            .find_map(|key| find_value(information, key))
+            .or_else(|| remote_object.version().clone())
+            .or_else(|| logger_version(logs));
+
+        let os_version = OS_VERSION_KEYS.iter().find_map(|(key, prefix)| {
+            find_value(information, key).map(|value| prefix.to_string() + &value)
+        });
+
+        let device_model = DEVICE_MODEL_KEYS
+            .iter()
+            .find_map(|key| find_value(information, key))
+            .map(
+                |model| match find_value(information, DEVICE_MANUFACTURER_KEY) {
+                    Some(manufacturer) if !model.starts_with(&manufacturer) => {
+                        format!("{manufacturer} {model}")
+                    }
+                    _ => model,
+                },
+            );
+
+        let (first, last) = time_range(logs, timestamps);
+        let date_time = |entry: &LogEntry| timestamps.date_time(&entry.timestamp);
+
+        Self {
+            platform: remote_object.platform().to_string(),
+            app: name.map(|name| name.app_id.to_string()),
+            app_version,
+            os_version,
+            device_model,
+            first_entry: first
+                .map(|entry| entry.timestamp.clone())
+                .or_else(|| name.map(|name| name.file_time.to_string())),
+            last_entry: last.map(|entry| entry.timestamp.clone()),
+            duration: first
+                .and_then(date_time)
+                .zip(last.and_then(date_time))
+                .map(|(first, last)| timeline::format_duration(last - first)),
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap()
+    }
+
+    /// Renders the summary, with the timestamps of the first and last entries in the selected
+    /// timezone.
+    pub fn view(&self, timestamps: &TimestampFormatter) -> Html {
+        let first_entry = self
+            .first_entry
+            .as_deref()
+            .map(|timestamp| timestamps.displayed(timestamp).into_owned());
+        let last_entry = self
+            .last_entry
+            .as_deref()
+            .map(|timestamp| timestamps.displayed(timestamp).into_owned());
+
+        let rows = [
+            ("Platform", Some(&self.platform)),
+            ("App", self.app.as_ref()),
+            ("App version", self.app_version.as_ref()),
+            ("OS version", self.os_version.as_ref()),
+            ("Device model", self.device_model.as_ref()),
+            ("First entry", first_entry.as_ref()),
+            ("Last entry", last_entry.as_ref()),
+            ("Duration", self.duration.as_ref()),
+        ];
+
+        html! {
+            <Message heading="Summary" classes={classes!("mb-8", "!mx-0")}>
+                <Table classes={classes!("font-mono", "text-sm")}>
+                    <tbody>
+                        {
+                            for rows.into_iter().filter_map(|(key, value)| value.map(|value| html! {
+                                <TableRow>
+                                    <TableItem>{ key }</TableItem>
+                                    <TableItem>{ value }</TableItem>
+                                </TableRow>
+                            }))
+                        }
+                    </tbody>
+                </Table>
+            </Message>
+        }
+    }
+}
+
+/// First value with the given key (case-insensitive) in the information sections, if any.
+fn find_value(sections: &[Section<InfoEntry>], key: &str) -> Option<String> {
+    sections.iter().find_map(|section| {
+        section
+            .content
+            .iter()
+            .find_map(|entry| match entry {
+                InfoEntry::KeyValue(k, Value::Generic(value))
+                | InfoEntry::KeyEnabledValue(k, _, Some(Value::Generic(value)))
+                    if k.eq_ignore_ascii_case(key) && !value.is_empty() =>
+                {
+                    Some(value.clone())
+                }
+                _ => None,
+            })
+            .or_else(|| find_value(&section.subsections, key))
+    })
+}
+
+fn all_entries(sections: &[Section<LogEntry>]) -> Vec<&LogEntry> {
+    sections
+        .iter()
+        .flat_map(|section| {
+            section
+                .content
+                .iter()
+                .chain(all_entries(&section.subsections))
+        })
+        .collect()
+}
+
+/// Version of the app that logged the latest logger entry (Android only).
+fn logger_version(sections: &[Section<LogEntry>]) -> Option<String> {
+    all_entries(sections)
+        .into_iter()
+        .rev()
+        .find_map(|entry| match &entry.meta {
+            PlatformMetadata::AndroidLogger { version, .. } => Some(version.clone()),
+            _ => None,
+        })
+}
+
+/// Earliest and latest entries, by their timestamps where possible.
+fn time_range<'a>(
+    sections: &'a [Section<LogEntry>],
+    timestamps: &TimestampFormatter,
+) -> (Option<&'a LogEntry>, Option<&'a LogEntry>) {
+    let entries = all_entries(sections);
+
+    let dated = entries.iter().filter_map(|entry| {
+        timestamps
+            .date_time(&entry.timestamp)
+            .map(|date_time| (date_time, *entry))
+    });
+
+    match (
+        dated.clone().min_by_key(|(date_time, _)| *date_time),
+        dated.max_by_key(|(date_time, _)| *date_time),
+    ) {
+        (Some((_, first)), Some((_, last))) => (Some(first), Some(last)),
+        _ => (entries.first().copied(), entries.last().copied()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parsers::test_entry, DisplayTimezone, Platform};
+
+    fn test_key_value(key: &str, value: &str) -> InfoEntry {
+        InfoEntry::KeyValue(key.to_owned(), Value::Generic(value.to_owned()))
+    }
+
+    #[test]
+    fn summary_android() {
+        let content = Content {
+            information: vec![Section {
+                name: "SYSINFO".to_owned(),
+                content: vec![
+                    test_key_value("Manufacturer", "Google"),
+                    test_key_value("Model", "Pixel 6"),
+                    test_key_value("Android", "13 (TQ1A.230105.002)"),
+                ],
+                subsections: vec![],
+            }],
+            logs: vec![Section {
+                name: "LOGGER".to_owned(),
+                content: vec![
+                    test_entry(
+                        "2023-01-22 12:00:00.000 +01:00",
+                        "6.1.2",
+                        "main",
+                        "Tag",
+                        "Message",
+                    ),
+                    test_entry(
+                        "2023-01-22 13:05:00.000 +01:00",
+                        "6.2.0",
+                        "main",
+                        "Tag",
+                        "Message",
+                    ),
+                ],
+                subsections: vec![],
+            }],
+        };
+
+        assert_eq!(
+            Summary::new(
+                &RemoteObject::new_unchecked(Platform::Android, None, "key"),
+                None,
+                Some(&content),
+                &TimestampFormatter::new(DisplayTimezone::Original, None)
+            ),
+            Summary {
+                platform: "Android".to_owned(),
+                app: None,
+                app_version: Some("6.2.0".to_owned()),
+                os_version: Some("Android 13 (TQ1A.230105.002)".to_owned()),
+                device_model: Some("Google Pixel 6".to_owned()),
+                first_entry: Some("2023-01-22 12:00:00.000 +01:00".to_owned()),
+                last_entry: Some("2023-01-22 13:05:00.000 +01:00".to_owned()),
+                duration: Some("1 h 5 min".to_owned()),
+            }
+        );
+    }
+
+    #[test]
+    fn summary_desktop() {
+        let content = Content {
+            information: vec![Section {
+                name: "System info".to_owned(),
+                content: vec![
+                    test_key_value("App version", "6.2.0"),
+                    test_key_value("OS version", "Windows 10.0.19045"),
+                ],
+                subsections: vec![],
+            }],
+            logs: vec![],
+        };
+
+        assert_eq!(
+            Summary::new(
+                &RemoteObject::new_unchecked(Platform::Desktop, Some("6.1.0".to_owned()), "key"),
+                None,
+                Some(&content),
+                &TimestampFormatter::new(DisplayTimezone::Original, None)
+            ),
+            Summary {
+                platform: "Desktop".to_owned(),
+                app_version: Some("6.2.0".to_owned()),
+                os_version: Some("Windows 10.0.19045".to_owned()),
+                ..Default::default()
+            }
+        );
+    }
+}
@@ -0,0 +1,351 @@
+use std::collections::{BTreeSet, HashMap};
+
+use yew::{html::Scope, prelude::*};
+
+use crate::{
+    components::*,
+    parsers::{Content, LogEntry, Section},
+    timezone::TimestampFormatter,
+    Highlighter, Model, Msg, RenderedLogSection, SearchQuery,
+};
+
+/// Number of the most active lanes that are visible unless hidden explicitly.
+const DEFAULT_VISIBLE_LANE_COUNT: usize = 8;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Lane {
+    pub thread_id: String,
+    pub count: usize,
+}
+
+/// Which lanes (threads) are displayed, in addition to the most active ones.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SwimlaneOptions {
+    pub shown: BTreeSet<String>,
+    pub hidden: BTreeSet<String>,
+    /// Displayed first, in this order, regardless of the other options.
+    pub pinned: Vec<String>,
+}
+
+impl SwimlaneOptions {
+    fn is_visible(&self, thread_id: &str, rank: usize) -> bool {
+        self.pinned.iter().any(|pinned| pinned == thread_id)
+            || self.shown.contains(thread_id)
+            || (rank < DEFAULT_VISIBLE_LANE_COUNT && !self.hidden.contains(thread_id))
+    }
+
+    /// Visible lanes out of `lanes` (which are sorted by activity), pinned ones first.
+    pub fn visible_lanes<'a>(&self, lanes: &'a [Lane]) -> Vec<&'a Lane> {
+        let mut visible = self
+            .pinned
+            .iter()
+            .filter_map(|pinned| lanes.iter().find(|lane| &lane.thread_id == pinned))
+            .collect::<Vec<_>>();
+
+        visible.extend(lanes.iter().enumerate().filter_map(|(rank, lane)| {
+            (!self.pinned.contains(&lane.thread_id) && self.is_visible(&lane.thread_id, rank))
+                .then_some(lane)
+        }));
+
+        visible
+    }
+
+    pub fn show(&mut self, thread_id: String) {
+        self.hidden.remove(&thread_id);
+        self.shown.insert(thread_id);
+    }
+
+    pub fn hide(&mut self, thread_id: String) {
+        self.shown.remove(&thread_id);
+        self.hidden.insert(thread_id);
+    }
+
+    pub fn toggle_pinned(&mut self, thread_id: String) {
+        match self.pinned.iter().position(|pinned| *pinned == thread_id) {
+            Some(position) => {
+                self.pinned.remove(position);
+            }
+            None => {
+                self.hidden.remove(&thread_id);
+                self.pinned.push(thread_id);
+            }
+        }
+    }
+}
+
+/// Threads that the entries were logged from, most active first.
+pub fn lanes<'a>(entries: impl Iterator<Item = &'a LogEntry>) -> Vec<Lane> {
+    let mut counts: HashMap<&str, (usize, usize)> = HashMap::new();
+
+    for (index, entry) in entries.enumerate() {
+        if let Some(thread_id) = entry.meta.thread_id() {
+            counts.entry(thread_id).or_insert((index, 0)).1 += 1;
+        }
+    }
+
+    let mut lanes = counts.into_iter().collect::<Vec<_>>();
+    lanes.sort_by(|(_, (a_first, a_count)), (_, (b_first, b_count))| {
+        b_count.cmp(a_count).then_with(|| a_first.cmp(b_first))
+    });
+
+    lanes
+        .into_iter()
+        .map(|(thread_id, (_, count))| Lane {
+            thread_id: thread_id.to_owned(),
+            count,
+        })
+        .collect()
+}
+
+impl Content {
+    pub fn view_swimlanes(
+        &self,
+        query: &SearchQuery,
+        highlighter: &Highlighter,
+        timestamps: &TimestampFormatter,
+        options: &SwimlaneOptions,
+        link: &Scope<Model>,
+    ) -> Vec<RenderedLogSection> {
+        self.logs
+            .iter()
+            .filter(|section| section.has_threads())
+            .map(|section| {
+                section.view_swimlanes(
+                    query,
+                    highlighter,
+                    timestamps,
+                    options,
+                    TitleLevel::H2,
+                    link,
+                )
+            })
+            .collect()
+    }
+}
+
+impl Section<LogEntry> {
+    pub fn has_threads(&self) -> bool {
+        self.content
+            .iter()
+            .any(|entry| entry.meta.thread_id().is_some())
+            || self.subsections.iter().any(Section::has_threads)
+    }
+
+    pub fn view_swimlanes(
+        &self,
+        query: &SearchQuery,
+        highlighter: &Highlighter,
+        timestamps: &TimestampFormatter,
+        options: &SwimlaneOptions,
+        level: TitleLevel,
+        link: &Scope<Model>,
+    ) -> RenderedLogSection {
+        let entries = self.displayed_entries(query, highlighter, timestamps);
+        let lanes = lanes(entries.clone().map(|(_, entry)| entry));
+        let visible_lanes = options.visible_lanes(&lanes);
+
+        let entries_to_display = entries.filter(|(_, entry)| {
+            visible_lanes
+                .iter()
+                .any(|lane| Some(lane.thread_id.as_str()) == entry.meta.thread_id())
+        });
+
+        let displayed_count = entries_to_display.clone().count();
+
+        let html = if lanes.is_empty() {
+            html! {}
+        } else {
+            html! {
+                <>
+                    { view_lane_picker(&lanes, &visible_lanes, options, link) }
+
+                    <Table>
+                        <thead>
+                            <TableRow classes={classes!("text-left")}>
+                                <TableItem tag="th"><Icon fixed_width_height=false icon={classes!("fas", "fa-hashtag")}/></TableItem>
+                                <TableItem tag="th" classes={classes!("min-w-[250px]")}>{ "Timestamp" }</TableItem>
+                                { for visible_lanes.iter().map(|lane| view_lane_header(lane, options, link)) }
+                            </TableRow>
+                        </thead>
+                        <tbody class="font-mono">
+                            {
+                                for entries_to_display.map(|(number, entry)| html! {
+                                    <TableRow classes={entry.level.unwrap_or_default().color()}>
+                                        <TableItem>{ number }</TableItem>
+                                        <TableItem>{ timestamps.view(&entry.timestamp, highlighter, None) }</TableItem>
+                                        {
+                                            for visible_lanes.iter().map(|lane| {
+                                                if Some(lane.thread_id.as_str()) == entry.meta.thread_id() {
+                                                    html! {
+                                                        <TableItem classes={classes!("min-w-[300px]")}>
+                                                            <span class="font-bold">{ highlighter.view(entry.meta.tag().unwrap_or_default(), None) }</span>
+                                                            <pre>{ highlighter.view(&entry.message, None) }</pre>
+                                                        </TableItem>
+                                                    }
+                                                } else {
+                                                    html! { <TableItem /> }
+                                                }
+                                            })
+                                        }
+                                    </TableRow>
+                                })
+                            }
+                        </tbody>
+                    </Table>
+                </>
+            }
+        };
+
+        RenderedLogSection {
+            level,
+            title: self.name.clone(),
+            displayed_count,
+            total_count: self.content.len(),
+            html,
+            subsections: self
+                .subsections
+                .iter()
+                .filter(|subsection| subsection.has_threads())
+                .map(|subsection| {
+                    subsection.view_swimlanes(
+                        query,
+                        highlighter,
+                        timestamps,
+                        options,
+                        level.incremented().unwrap(),
+                        link,
+                    )
+                })
+                .collect(),
+        }
+    }
+}
+
+fn view_lane_header(lane: &Lane, options: &SwimlaneOptions, link: &Scope<Model>) -> Html {
+    let pinned = options.pinned.contains(&lane.thread_id);
+    let thread_id = lane.thread_id.clone();
+
+    html! {
+        <TableItem tag="th">
+            <div class="flex items-center whitespace-nowrap">
+                <Button
+                    size={ButtonSize::Small}
+                    icon={classes!("fas", "fa-thumbtack")}
+                    active={pinned}
+                    on_click={link.callback(move |_| Msg::ToggleLanePinned(thread_id.clone()))}
+                />
+                <span class="ml-2">{ &lane.thread_id }</span>
+            </div>
+        </TableItem>
+    }
+}
+
+fn view_lane_picker(
+    lanes: &[Lane],
+    visible_lanes: &[&Lane],
+    options: &SwimlaneOptions,
+    link: &Scope<Model>,
+) -> Html {
+    html! {
+        <div class="flex flex-wrap gap-2 mb-4 not-prose">
+            {
+                for lanes.iter().map(|lane| {
+                    let visible = visible_lanes.contains(&lane);
+                    let pinned = options.pinned.contains(&lane.thread_id);
+                    let thread_id = lane.thread_id.clone();
+
+                    html! {
+                        <Button
+                            classes={classes!("rounded-2xl")}
+                            size={ButtonSize::Small}
+                            active={visible}
+                            disabled={pinned}
+                            text={format!("{} ({})", lane.thread_id, lane.count)}
+                            on_click={link.callback(move |_| if visible {
+                                Msg::HideLane(thread_id.clone())
+                            } else {
+                                Msg::ShowLane(thread_id.clone())
+                            })}
+                        />
+                    }
+                })
+            }
+        </div>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parsers::test_entry;
+
+    fn test_lanes(count: usize) -> Vec<Lane> {
+        (0..count)
+            .map(|i| Lane {
+                thread_id: i.to_string(),
+                count: count - i,
+            })
+            .collect()
+    }
+
+    fn thread_ids(lanes: Vec<&Lane>) -> Vec<&str> {
+        lanes.iter().map(|lane| lane.thread_id.as_str()).collect()
+    }
+
+    #[test]
+    fn lanes_sorted_by_activity() {
+        let entries = ["main", "db", "db", "ws", "db", "ws"]
+            .map(|thread_id| test_entry("", "1", thread_id, "Tag", ""));
+
+        assert_eq!(
+            lanes(entries.iter()),
+            vec![
+                Lane {
+                    thread_id: "db".to_owned(),
+                    count: 3
+                },
+                Lane {
+                    thread_id: "ws".to_owned(),
+                    count: 2
+                },
+                Lane {
+                    thread_id: "main".to_owned(),
+                    count: 1
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn visible_lanes_default() {
+        let lanes = test_lanes(10);
+
+        assert_eq!(
+            thread_ids(SwimlaneOptions::default().visible_lanes(&lanes)),
+            vec!["0", "1", "2", "3", "4", "5", "6", "7"]
+        );
+    }
+
+    #[test]
+    fn visible_lanes_toggled_and_pinned() {
+        let lanes = test_lanes(10);
+        let mut options = SwimlaneOptions::default();
+
+        options.hide("1".to_owned());
+        options.show("9".to_owned());
+        options.toggle_pinned("5".to_owned());
+        options.toggle_pinned("8".to_owned());
+
+        assert_eq!(
+            thread_ids(options.visible_lanes(&lanes)),
+            vec!["5", "8", "0", "2", "3", "4", "6", "7", "9"]
+        );
+
+        options.toggle_pinned("8".to_owned());
+
+        assert_eq!(
+            thread_ids(options.visible_lanes(&lanes)),
+            vec!["5", "0", "2", "3", "4", "6", "7", "9"]
+        );
+    }
+}
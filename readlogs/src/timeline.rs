@@ -0,0 +1,262 @@
+use std::fmt;
+
+use chrono::Duration;
+
+use crate::{
+    parsers::{LogEntry, LogFormat, PlatformMetadata},
+    timezone::TimestampFormatter,
+};
+
+/// Something notable that happened between two consecutive log entries.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TimelineEvent {
+    Gap(Duration),
+    ProcessRestart { from: String, to: String },
+    VersionChange { from: String, to: String },
+    Launch,
+}
+
+impl fmt::Display for TimelineEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TimelineEvent::Gap(duration) => write!(f, "{} gap", format_duration(*duration)),
+            TimelineEvent::ProcessRestart { from, to } => {
+                write!(f, "process restarted (pid {from} → {to})")
+            }
+            TimelineEvent::VersionChange { from, to } => write!(f, "version {from} → {to}"),
+            TimelineEvent::Launch => write!(f, "app launched"),
+        }
+    }
+}
+
+/// Formats the duration using its largest units, e.g. `1 h 5 min` or `42 min`.
+pub fn format_duration(duration: Duration) -> String {
+    let sign = if duration < Duration::zero() { "-" } else { "" };
+    let duration = if duration < Duration::zero() {
+        -duration
+    } else {
+        duration
+    };
+
+    let days = duration.num_days();
+    let hours = duration.num_hours() % 24;
+    let minutes = duration.num_minutes() % 60;
+    let seconds = duration.num_seconds() % 60;
+    let milliseconds = duration.num_milliseconds() % 1000;
+
+    let text = if days > 0 {
+        format!("{days} d {hours} h")
+    } else if hours > 0 {
+        format!("{hours} h {minutes} min")
+    } else if minutes > 0 {
+        format!("{minutes} min")
+    } else if seconds > 0 {
+        format!("{seconds}.{:03} s", milliseconds)
+    } else {
+        format!("{milliseconds} ms")
+    };
+
+    sign.to_owned() + &text
+}
+
+/// Events that happened right before the entry with the given index (in `entries`),
+/// for every entry that has any.
+///
+/// Gaps are only reported if they are at least `gap_threshold` long (if it's specified).
+pub fn events(
+    entries: &[LogEntry],
+    format: &dyn LogFormat,
+    gap_threshold: Option<Duration>,
+    timestamps: &TimestampFormatter,
+) -> Vec<(usize, Vec<TimelineEvent>)> {
+    let mut result = vec![];
+
+    for (index, entry) in entries.iter().enumerate() {
+        let mut events = vec![];
+        let previous = index.checked_sub(1).map(|index| &entries[index]);
+
+        if let Some(previous) = previous {
+            if let (Some(threshold), Some(previous_time), Some(time)) = (
+                gap_threshold,
+                timestamps.date_time(&previous.timestamp),
+                timestamps.date_time(&entry.timestamp),
+            ) {
+                let gap = time - previous_time;
+
+                if gap >= threshold {
+                    events.push(TimelineEvent::Gap(gap));
+                }
+            }
+
+            match (&previous.meta, &entry.meta) {
+                (
+                    PlatformMetadata::AndroidLogcat {
+                        process_id: from, ..
+                    },
+                    PlatformMetadata::AndroidLogcat { process_id: to, .. },
+                ) if from != to => events.push(TimelineEvent::ProcessRestart {
+                    from: from.clone(),
+                    to: to.clone(),
+                }),
+                (
+                    PlatformMetadata::AndroidLogger { version: from, .. },
+                    PlatformMetadata::AndroidLogger { version: to, .. },
+                ) if from != to => events.push(TimelineEvent::VersionChange {
+                    from: from.clone(),
+                    to: to.clone(),
+                }),
+                _ => {}
+            }
+        }
+
+        // Multiple consecutive entries may be logged while launching.
+        if format.is_launch_marker(entry)
+            && !matches!(previous, Some(previous) if format.is_launch_marker(previous))
+        {
+            events.push(TimelineEvent::Launch);
+        }
+
+        if !events.is_empty() {
+            result.push((index, events));
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+
+    use super::*;
+    use crate::{
+        parsers::{format::test_format, test_entry},
+        DisplayTimezone, LogLevel,
+    };
+
+    fn test_timestamps() -> TimestampFormatter {
+        TimestampFormatter::new(DisplayTimezone::Original, None)
+    }
+
+    fn test_logcat_entry(timestamp: &str, process_id: &str) -> LogEntry {
+        LogEntry {
+            meta: PlatformMetadata::AndroidLogcat {
+                process_id: process_id.to_owned(),
+                thread_id: "1".to_owned(),
+                tag: "Tag".to_owned(),
+            },
+            ..test_entry(timestamp, "1", "1", "Tag", "Message")
+        }
+    }
+
+    #[test_case(Duration::milliseconds(15) => "15 ms"; "milliseconds")]
+    #[test_case(Duration::milliseconds(1500) => "1.500 s"; "seconds")]
+    #[test_case(Duration::minutes(42) => "42 min"; "minutes")]
+    #[test_case(Duration::minutes(65) => "1 h 5 min"; "hours")]
+    #[test_case(Duration::hours(50) => "2 d 2 h"; "days")]
+    #[test_case(Duration::minutes(-3) => "-3 min"; "negative")]
+    fn format_duration_ok(duration: Duration) -> String {
+        format_duration(duration)
+    }
+
+    #[test]
+    fn events_logcat() {
+        let entries = vec![
+            test_logcat_entry("2023-01-22 12:00:00.000", "100"),
+            test_logcat_entry("2023-01-22 12:01:00.000", "100"),
+            test_logcat_entry("2023-01-22 12:43:00.000", "200"),
+            test_logcat_entry("2023-01-22 12:43:01.000", "200"),
+        ];
+
+        assert_eq!(
+            events(
+                &entries,
+                test_format("android"),
+                Some(Duration::minutes(5)),
+                &test_timestamps()
+            ),
+            vec![(
+                2,
+                vec![
+                    TimelineEvent::Gap(Duration::minutes(42)),
+                    TimelineEvent::ProcessRestart {
+                        from: "100".to_owned(),
+                        to: "200".to_owned(),
+                    },
+                ]
+            )]
+        );
+    }
+
+    #[test]
+    fn events_logger() {
+        let entries = vec![
+            test_entry(
+                "2023-01-22 12:00:00.000 +01:00",
+                "6.1.2",
+                "main",
+                "Tag",
+                "Message",
+            ),
+            test_entry(
+                "2023-01-22 12:00:01.000 +01:00",
+                "6.2.0",
+                "main",
+                "ApplicationContext",
+                "onCreate()",
+            ),
+            test_entry(
+                "2023-01-22 12:00:02.000 +01:00",
+                "6.2.0",
+                "main",
+                "Tag",
+                "Message",
+            ),
+        ];
+
+        assert_eq!(
+            events(&entries, test_format("android"), None, &test_timestamps()),
+            vec![(
+                1,
+                vec![
+                    TimelineEvent::VersionChange {
+                        from: "6.1.2".to_owned(),
+                        to: "6.2.0".to_owned(),
+                    },
+                    TimelineEvent::Launch,
+                ]
+            )]
+        );
+    }
+
+    #[test]
+    fn events_desktop() {
+        let entry = |timestamp: &str, message: &str| LogEntry {
+            timestamp: timestamp.to_owned(),
+            level: Some(LogLevel::Info),
+            meta: PlatformMetadata::Desktop,
+            message: message.to_owned(),
+        };
+
+        let entries = vec![
+            entry("2023-01-22 12:00:00.000 UTC", "Message"),
+            entry("2023-01-22 14:00:00.000 UTC", "starting version 6.2.0"),
+        ];
+
+        assert_eq!(
+            events(
+                &entries,
+                test_format("desktop"),
+                Some(Duration::hours(1)),
+                &test_timestamps()
+            ),
+            vec![(
+                1,
+                vec![
+                    TimelineEvent::Gap(Duration::hours(2)),
+                    TimelineEvent::Launch,
+                ]
+            )]
+        );
+    }
+}
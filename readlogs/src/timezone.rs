@@ -0,0 +1,206 @@
+use std::borrow::Cow;
+
+use chrono::prelude::*;
+use derive_more::Display;
+use strum_macros::{EnumIter, EnumString};
+use yew::prelude::*;
+
+use crate::{
+    parsers::{LogEntry, Section},
+    Highlighter,
+};
+
+const EXPLICIT_OFFSET_FORMAT: &str = "%Y-%m-%d %H:%M:%S%.f %:z";
+const NAIVE_FORMAT: &str = "%Y-%m-%d %H:%M:%S%.f";
+const DISPLAY_FORMAT: &str = "%Y-%m-%d %H:%M:%S%.3f %:z";
+
+/// Timezone that timestamps are displayed in.
+#[derive(Debug, Display, Clone, Copy, Default, PartialEq, Eq, EnumIter, EnumString)]
+pub enum DisplayTimezone {
+    /// As they were logged.
+    #[default]
+    #[display(fmt = "As logged")]
+    #[strum(serialize = "As logged")]
+    Original,
+    #[display(fmt = "UTC")]
+    #[strum(serialize = "UTC")]
+    Utc,
+    /// Of the device that the logs were submitted from.
+    #[display(fmt = "Submitter's timezone")]
+    #[strum(serialize = "Submitter's timezone")]
+    Submitter,
+    #[display(fmt = "Your timezone")]
+    #[strum(serialize = "Your timezone")]
+    Browser,
+}
+
+/// Renders timestamps stored by the parsers in the selected timezone.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimestampFormatter {
+    timezone: DisplayTimezone,
+    submitter_offset: Option<FixedOffset>,
+}
+
+impl TimestampFormatter {
+    pub fn new(timezone: DisplayTimezone, submitter_offset: Option<FixedOffset>) -> Self {
+        Self {
+            timezone,
+            submitter_offset,
+        }
+    }
+
+    fn utc() -> FixedOffset {
+        FixedOffset::east_opt(0).unwrap()
+    }
+
+    /// Parses the timestamp, assuming that ones without a timezone (e.g. from logcat) are in the
+    /// submitter's timezone, if it's known.
+    pub fn date_time(&self, timestamp: &str) -> Option<DateTime<FixedOffset>> {
+        parse_timestamp(timestamp, self.submitter_offset.unwrap_or_else(Self::utc))
+    }
+
+    /// The timestamp in the selected timezone, or `None` if it should be displayed as is.
+    pub fn format(&self, timestamp: &str) -> Option<String> {
+        if self.timezone == DisplayTimezone::Original {
+            return None;
+        }
+
+        let date_time = self.date_time(timestamp)?;
+
+        let date_time = match self.timezone {
+            DisplayTimezone::Original => return None,
+            DisplayTimezone::Utc => date_time.with_timezone(&Self::utc()),
+            DisplayTimezone::Submitter => date_time.with_timezone(&self.submitter_offset?),
+            DisplayTimezone::Browser => {
+                let local = date_time.with_timezone(&Local);
+                local.with_timezone(&local.offset().fix())
+            }
+        };
+
+        Some(date_time.format(DISPLAY_FORMAT).to_string())
+    }
+
+    /// The timestamp as it's displayed, which is what search queries are matched against.
+    pub fn displayed<'a>(&self, timestamp: &'a str) -> Cow<'a, str> {
+        match self.format(timestamp) {
+            Some(formatted) => Cow::Owned(formatted),
+            None => Cow::Borrowed(timestamp),
+        }
+    }
+
+    /// Renders the timestamp in the selected timezone, with the original one in a tooltip.
+    /// Its matches are numbered from `first_match` if they can be navigated between.
+    pub fn view(
+        &self,
+        timestamp: &str,
+        highlighter: &Highlighter,
+        first_match: Option<usize>,
+    ) -> Html {
+        match self.format(timestamp) {
+            Some(formatted) => html! {
+                <span title={timestamp.to_owned()}>{ highlighter.view(&formatted, first_match) }</span>
+            },
+            None => highlighter.view(timestamp, first_match),
+        }
+    }
+}
+
+/// Date and time of a timestamp as formatted by the parsers, e.g. `1234-01-23 12:34:56.789 UTC`,
+/// along with what follows them (the time zone), if they can be parsed.
+pub fn split_timestamp(timestamp: &str) -> Option<(NaiveDateTime, &str)> {
+    let (date_time, rest) = match timestamp.match_indices(' ').nth(1) {
+        Some((index, _)) => (&timestamp[..index], timestamp[index..].trim_start()),
+        None => (timestamp, ""),
+    };
+
+    NaiveDateTime::parse_from_str(date_time, NAIVE_FORMAT)
+        .ok()
+        .map(|date_time| (date_time, rest))
+}
+
+/// Parses a timestamp back from the format it was stored in by the parsers.
+///
+/// Timestamps in UTC end with `UTC`, and ones without a timezone that can be parsed (e.g. from
+/// logcat) are assumed to have the offset.
+pub fn parse_timestamp(
+    timestamp: &str,
+    naive_offset: FixedOffset,
+) -> Option<DateTime<FixedOffset>> {
+    if let Ok(date_time) = DateTime::parse_from_str(timestamp, EXPLICIT_OFFSET_FORMAT) {
+        return Some(date_time);
+    }
+
+    let (date_time, time_zone) = split_timestamp(timestamp)?;
+    let offset = match time_zone {
+        "UTC" => FixedOffset::east_opt(0).unwrap(),
+        _ => naive_offset,
+    };
+
+    offset.from_local_datetime(&date_time).single()
+}
+
+/// Offset of the submitter's device, based on the first entry whose timestamp has one
+/// (other than UTC, which is used when the timezone is unknown).
+pub fn submitter_offset(sections: &[Section<LogEntry>]) -> Option<FixedOffset> {
+    sections.iter().find_map(|section| {
+        section
+            .content
+            .iter()
+            .find_map(|entry| {
+                DateTime::parse_from_str(&entry.timestamp, EXPLICIT_OFFSET_FORMAT)
+                    .ok()
+                    .map(|date_time| *date_time.offset())
+            })
+            .or_else(|| submitter_offset(&section.subsections))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+
+    use super::*;
+
+    #[test_case(DisplayTimezone::Original, "2023-01-22 12:00:00.000 +01:00" => None; "original")]
+    #[test_case(DisplayTimezone::Utc, "2023-01-22 12:00:00.000 +01:00" => Some("2023-01-22 11:00:00.000 +00:00".to_owned()); "logger to utc")]
+    #[test_case(DisplayTimezone::Submitter, "2023-01-22 11:00:00.000 UTC" => Some("2023-01-22 14:00:00.000 +03:00".to_owned()); "utc to submitter")]
+    #[test_case(DisplayTimezone::Utc, "2023-01-22 12:00:00.000" => Some("2023-01-22 09:00:00.000 +00:00".to_owned()); "naive to utc")]
+    #[test_case(DisplayTimezone::Utc, "not a timestamp" => None; "invalid")]
+    fn format_ok(timezone: DisplayTimezone, timestamp: &str) -> Option<String> {
+        TimestampFormatter::new(timezone, FixedOffset::east_opt(3 * 3600)).format(timestamp)
+    }
+
+    #[test_case("1234-01-23 12:34:56.789 UTC" => Some(("1234-01-23 12:34:56.789".to_owned(), "UTC")); "with time zone")]
+    #[test_case("1234-01-23 12:34:56" => Some(("1234-01-23 12:34:56".to_owned(), "")); "without fraction or time zone")]
+    #[test_case("yesterday" => None; "invalid")]
+    fn split_timestamp_ok(timestamp: &str) -> Option<(String, &str)> {
+        split_timestamp(timestamp)
+            .map(|(date_time, rest)| (date_time.format("%Y-%m-%d %H:%M:%S%.f").to_string(), rest))
+    }
+
+    #[test_case("2023-01-22 12:00:00.000 +01:00" => Some("2023-01-22 12:00:00 +01:00".to_owned()); "explicit offset")]
+    #[test_case("2023-01-22 12:00:00.000 UTC" => Some("2023-01-22 12:00:00 +00:00".to_owned()); "utc")]
+    #[test_case("2023-01-22 12:00:00.000" => Some("2023-01-22 12:00:00 +03:00".to_owned()); "naive")]
+    #[test_case("2023-01-22 12:00:00.000 Mars/Olympus" => Some("2023-01-22 12:00:00 +03:00".to_owned()); "unknown timezone")]
+    fn parse_timestamp_ok(timestamp: &str) -> Option<String> {
+        parse_timestamp(timestamp, FixedOffset::east_opt(3 * 3600).unwrap())
+            .map(|date_time| date_time.to_string())
+    }
+
+    #[test_case(DisplayTimezone::Original => "2023-01-22 12:00:00.000 +01:00"; "original")]
+    #[test_case(DisplayTimezone::Utc => "2023-01-22 11:00:00.000 +00:00"; "converted")]
+    fn displayed(timezone: DisplayTimezone) -> String {
+        TimestampFormatter::new(timezone, None)
+            .displayed("2023-01-22 12:00:00.000 +01:00")
+            .into_owned()
+    }
+
+    #[test]
+    fn format_submitter_unknown() {
+        assert_eq!(
+            TimestampFormatter::new(DisplayTimezone::Submitter, None)
+                .format("2023-01-22 11:00:00.000 UTC"),
+            None
+        );
+    }
+}
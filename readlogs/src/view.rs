@@ -1,31 +1,62 @@
 use std::rc::Rc;
 
+use chrono::Duration;
 use strum::IntoEnumIterator;
 use wasm_bindgen::JsCast;
 use web_sys::{Event, HtmlSelectElement};
 use yew::prelude::*;
 
-use crate::{components::*, *};
+use crate::{
+    components::*,
+    download::Progress,
+    keyboard::{self, Shortcut},
+    *,
+};
+
+/// Options for `DisplayOptions::gap_threshold`, in minutes.
+const GAP_THRESHOLD_OPTIONS: [i64; 5] = [1, 5, 15, 60, 360];
 
 impl super::Model {
     pub fn view_inner(&self, ctx: &Context<Self>) -> Html {
-        let file_picker = match &self.state {
+        let document = self.document();
+
+        let file_picker = match &document.state {
             State::Ready(Object::Multiple {
                 files,
                 active_filename,
             }) => html! {
                 <FilePicker
                     classes={classes!("mb-8")}
-                    files={files.keys().cloned().collect::<Vec<_>>()}
-                    selected_file={active_filename}
+                    files={
+                        files
+                            .iter()
+                            .map(|(name, file)| FilePickerEntry {
+                                name: name.clone(),
+                                status: file.status(),
+                                search_summary: document.search_summaries.get(name).copied().unwrap_or_default(),
+                            })
+                            .collect::<Vec<_>>()
+                    }
+                    selected_file={active_filename.clone()}
                     on_file_selected={ctx.link().callback(Msg::UpdateActiveFile)}
                 />
             },
             _ => html! {},
         };
 
-        let active_file = match &self.state {
-            State::Ready(_) => self.active_file().view(self.tab, &self.active_query),
+        let highlighter = document
+            .highlighter
+            .clone()
+            .with_current_match(document.current_match)
+            .with_cursor(document.cursor);
+
+        let active_file = match &document.state {
+            State::Ready(_) => document.active_file().view(
+                document,
+                &highlighter,
+                &self.display_options,
+                ctx.link(),
+            ),
             _ => html! {},
         };
 
@@ -44,10 +75,17 @@ impl super::Model {
             <>
                 <div class={wrapper_classes}>
                     <div class="mx-4">
+                        { self.view_document_tabs(ctx) }
                         { self.view_main_input(ctx) }
+                        { self.view_url_error() }
+                        { self.view_found_logs(ctx) }
                         { self.view_help(ctx) }
+                        { self.view_recent_logs(ctx) }
+                        { self.view_fetch_settings(ctx) }
 
                         { file_picker }
+
+                        { self.view_pivots(ctx) }
                     </div>
 
                     <div class="mx-4 prose dark:prose-invert prose-sm max-w-max mt-8">
@@ -58,10 +96,44 @@ impl super::Model {
                 { self.view_footer() }
 
                 { self.view_display_config(ctx) }
+
+                { self.view_entry_details(ctx) }
+
+                { self.view_shortcuts(ctx) }
             </>
         }
     }
 
+    pub fn view_document_tabs(&self, ctx: &Context<Self>) -> Html {
+        // Until a debug log is opened, there is nothing to switch between.
+        if self.documents.len() < 2 && self.document().state.is_no_data() {
+            return html! {};
+        }
+
+        html! {
+            <DocumentTabs
+                classes={classes!("mb-4")}
+                documents={
+                    self.documents
+                        .iter()
+                        .map(|document| DocumentTabsEntry {
+                            label: document.label(),
+                            loading: document.state.is_loading(),
+                            failed: document.state.is_error(),
+                            search_summary: (document.state.is_ready()
+                                && document.active_query != SearchQuery::default())
+                                .then_some(document.search_summary),
+                        })
+                        .collect::<Vec<_>>()
+                }
+                active={self.active_document}
+                on_select={ctx.link().callback(Msg::SelectDocument)}
+                on_close={ctx.link().callback(Msg::CloseDocument)}
+                on_new={ctx.link().callback(|_| Msg::NewDocument)}
+            />
+        }
+    }
+
     pub fn view_main_input(&self, ctx: &Context<Self>) -> Html {
         html! {
             <div class="flex mb-8">
@@ -72,7 +144,7 @@ impl super::Model {
                     on_change={ctx.link().callback(Msg::UpdateUrl)}
                     on_submit_maybe={ctx.link().batch_callback(|actually: bool| actually.then_some(Msg::Start))}
                     placeholder="https://debuglogs.org/..."
-                    disabled={self.state.is_fetching()}
+                    disabled={self.document().state.is_loading()}
                     autofocus={true}
                 />
 
@@ -82,7 +154,9 @@ impl super::Model {
     }
 
     pub fn view_help(&self, ctx: &Context<Self>) -> Html {
-        match &self.state {
+        let document = self.document();
+
+        match &document.state {
             State::NoData => html! {
                 <Message>
                     { "Please enter a Signal " }
@@ -94,13 +168,11 @@ impl super::Model {
                     { "." }
                 </Message>
             },
-            State::Fetching => html! {
-                <Message
-                    heading="Progress"
-                    text="Fetching and parsing..."
-                    classes={classes!("animate-pulse")}
-                />
-            },
+            State::Downloading(progress) => {
+                self.view_progress("Downloading...", Some(progress), ctx)
+            }
+            State::Decompressing => self.view_progress("Decompressing...", None, ctx),
+            State::Parsing => self.view_progress("Parsing...", None, ctx),
             State::Error(e) => html! {
                 <Message error={true} heading="Error">
                     <CodeBlock text={Rc::new(format!("Error: {e:?}"))}/>
@@ -110,19 +182,120 @@ impl super::Model {
         }
     }
 
+    pub fn view_url_error(&self) -> Html {
+        match &self.url_error {
+            Some(e) => html! {
+                <Message classes={classes!("mb-8")} error={true} heading="Error">
+                    <CodeBlock text={Rc::new(format!("Error: {e:?}"))}/>
+                </Message>
+            },
+            None => html! {},
+        }
+    }
+
+    pub fn view_found_logs(&self, ctx: &Context<Self>) -> Html {
+        // A single log is simply opened.
+        if self.found_remote_objects.len() < 2 {
+            return html! {};
+        }
+
+        html! {
+            <FoundLogs
+                classes={classes!("mb-8")}
+                remote_objects={self.found_remote_objects.clone()}
+                on_open={ctx.link().callback(Msg::OpenUrl)}
+                on_open_all={ctx.link().callback(|_| Msg::OpenFoundRemoteObjects)}
+                on_dismiss={ctx.link().callback(|_| Msg::DismissFoundRemoteObjects)}
+            />
+        }
+    }
+
+    /// Shows the phase of loading the debug log, with the download progress if it's known.
+    pub fn view_progress(
+        &self,
+        text: &str,
+        progress: Option<&Progress>,
+        ctx: &Context<Self>,
+    ) -> Html {
+        let (bar, details) = match progress {
+            Some(progress) => (progress.fraction(), progress.text()),
+            None => (None, "".to_owned()),
+        };
+
+        // Without a known fraction, the bar is full and pulses instead.
+        let (width, bar_classes) = match bar {
+            Some(fraction) => (fraction * 100.0, classes!()),
+            None => (100.0, classes!("animate-pulse")),
+        };
+
+        html! {
+            <Message heading="Progress">
+                <div class="flex items-center gap-x-4 not-prose">
+                    <span class="shrink-0">{ text }</span>
+                    <div class="grow h-3 rounded-2xl bg-brand-bg dark:bg-brand-dark-bg">
+                        <div
+                            class={classes!(bar_classes, "h-3", "rounded-2xl", "bg-brand-primary-active", "dark:bg-brand-dark-primary-active")}
+                            style={format!("width: {width:.1}%")}
+                        />
+                    </div>
+                    <span class="shrink-0 text-sm font-mono">{ details }</span>
+                    <Button
+                        size={ButtonSize::Small}
+                        icon={classes!("fas", "fa-times")}
+                        text="Cancel"
+                        on_click={ctx.link().callback(|_| Msg::CancelFetch)}
+                    />
+                </div>
+            </Message>
+        }
+    }
+
+    pub fn view_recent_logs(&self, ctx: &Context<Self>) -> Html {
+        match &self.document().state {
+            State::NoData | State::Error(_) => html! {
+                <RecentLogs
+                    classes={classes!("mt-4")}
+                    logs={self.recent_logs.clone()}
+                    on_open={ctx.link().callback(Msg::OpenUrl)}
+                    on_label_changed={ctx.link().callback(|(key, label)| Msg::UpdateRecentLogLabel(key, label))}
+                    on_remove={ctx.link().callback(Msg::RemoveRecentLog)}
+                    on_clear={ctx.link().callback(|_| Msg::ClearCache)}
+                />
+            },
+            _ => html! {},
+        }
+    }
+
+    pub fn view_fetch_settings(&self, ctx: &Context<Self>) -> Html {
+        match &self.document().state {
+            State::NoData | State::Error(_) => html! {
+                <FetchSettings
+                    classes={classes!("mt-4")}
+                    mode={self.fetch_mode.clone()}
+                    health_check={self.health_check.clone()}
+                    on_mode_changed={ctx.link().callback(Msg::UpdateFetchMode)}
+                    on_health_check={ctx.link().callback(|_| Msg::CheckHealth)}
+                />
+            },
+            _ => html! {},
+        }
+    }
+
     pub fn view_submit_button(&self, size: ButtonSize, ctx: &Context<Self>) -> Html {
         html! {
             <Button
                 {size}
                 on_click={ctx.link().callback(|_| Msg::Start)}
-                disabled={self.state.is_fetching()}
+                disabled={self.document().state.is_loading()}
                 text="Read"
             />
         }
     }
 
     pub fn view_display_config(&self, ctx: &Context<Self>) -> Html {
-        if !self.state.is_ready() {
+        let document = self.document();
+
+        if !document.state.is_ready() {
             return html! {};
         }
 
@@ -136,7 +309,24 @@ impl super::Model {
 
                     <div class="flex grow">
                         <div class="flex grow">
-                            { for Tab::iter().map(|tab| self.view_tab_button(tab, ctx)) }
+                            {
+                                for Tab::iter()
+                                    .filter(|tab| tab.applicable_to(document.format()))
+                                    .map(|tab| self.view_tab_button(tab, ctx))
+                            }
+                        </div>
+
+                        <div>
+                            <Button
+                                classes={classes!(
+                                    "hidden",
+                                    "lg:block",
+                                    "ml-2",
+                                )}
+                                on_click={ctx.link().callback(|_| Msg::Shortcut(Shortcut::ToggleHelp))}
+                                icon={classes!("fas", "fa-keyboard")}
+                                active={self.show_shortcuts}
+                            />
                         </div>
 
                         <div>
@@ -166,10 +356,12 @@ impl super::Model {
     }
 
     pub fn view_search_toolbar_row(&self, ctx: &Context<Self>) -> Html {
-        match (&self.state, &self.tab) {
-            (State::Ready(_), Tab::Logs) => {
+        let document = self.document();
+
+        match (&document.state, &document.tab) {
+            (State::Ready(_), Tab::Logs | Tab::Threads) => {
                 let min_log_level_classes = classes!(
-                    self.pending_query.min_log_level.color(),
+                    document.pending_query.min_log_level.color(),
                     "rounded-l-2xl",
                     "border-brand-border",
                     "dark:border-brand-dark-border",
@@ -187,10 +379,18 @@ impl super::Model {
                     "dark:bg-brand-dark-bg",
                 );
 
+                let pending_query_valid = document.pending_query.pattern().is_ok();
+
+                let query_classes = if pending_query_valid {
+                    classes!()
+                } else {
+                    classes!("!ring", "!ring-rose-600", "dark:!ring-rose-400")
+                };
+
                 html! {
                     <div class="flex grow">
                         <select
-                            value={self.pending_query.min_log_level.to_string()}
+                            value={document.pending_query.min_log_level.to_string()}
                             onchange={ctx.link().callback(|event: Event|
                                 Msg::UpdateMinLogLevel(event.target().unwrap().dyn_into::<HtmlSelectElement>().unwrap().value())
                             )}
@@ -198,53 +398,296 @@ impl super::Model {
                         >
                             {
                                 for LogLevel::iter()
-                                    .filter(|variant| variant.applicable_to_platform(self.remote_object.as_ref().unwrap().platform()))
+                                    .filter(|variant| variant.applicable_to(document.format()))
                                     .map(|variant| html! {
-                                        <option selected={variant == self.pending_query.min_log_level}>{ variant }</option>
+                                        <option selected={variant == document.pending_query.min_log_level}>{ variant }</option>
                                     })
                             }
                         </select>
 
                         <Input
-                            value={self.pending_query.string.clone()}
+                            r#ref={self.search_input.clone()}
+                            classes={query_classes}
+                            value={document.pending_query.string.clone()}
                             on_change={ctx.link().callback(Msg::UpdateQuery)}
                             on_submit_maybe={ctx.link().batch_callback(|actually: bool| actually.then_some(Msg::ApplySearchQuery))}
                             placeholder={
                                 "Search ".to_owned()
-                                    + &self.pending_query.min_log_level.to_string().to_lowercase()
+                                    + &document.pending_query.min_log_level.to_string().to_lowercase()
                                     + " logs..."
                             }
                         />
 
+                        {
+                            match &document.active_query.tag {
+                                Some(tag) => html! {
+                                    <Button
+                                        on_click={ctx.link().callback(|_| Msg::ClearQueryTag)}
+                                        icon={classes!("fas", "fa-times")}
+                                        text={format!("Tag: {tag}")}
+                                    />
+                                },
+                                None => html! {},
+                            }
+                        }
+
+                        {
+                            match &document.active_query.message_pattern {
+                                Some(pattern) => html! {
+                                    <Button
+                                        on_click={ctx.link().callback(|_| Msg::ClearQueryPattern)}
+                                        icon={classes!("fas", "fa-times")}
+                                        text={format!("Pattern: {}", pattern.template)}
+                                    />
+                                },
+                                None => html! {},
+                            }
+                        }
+
+                        <Button
+                            on_click={ctx.link().callback(|_| Msg::UpdateQueryRegex)}
+                            icon={classes!("fas", "fa-asterisk")}
+                            active={document.pending_query.regex}
+                        />
+
                         <Button
                             on_click={ctx.link().callback(|_| Msg::ApplySearchQuery)}
-                            icon={classes!("fas", if self.pending_query == self.active_query {
+                            icon={classes!("fas", if document.pending_query == document.active_query {
                                 "fa-check"
                             } else {
                                 "fa-search"
                             })}
-                            disabled={self.pending_query == self.active_query}
+                            disabled={document.pending_query == document.active_query || !pending_query_valid}
                         />
+
+                        {
+                            if self.documents.len() > 1 {
+                                html! {
+                                    <Button
+                                        on_click={ctx.link().callback(|_| Msg::SearchAllDocuments)}
+                                        icon={classes!("fas", "fa-clone")}
+                                        text="All logs"
+                                        disabled={!pending_query_valid}
+                                    />
+                                }
+                            } else {
+                                html! {}
+                            }
+                        }
+
+                        {
+                            if document.tab.is_logs() {
+                                html! {
+                                    <>
+                                        { self.view_match_navigation(ctx) }
+                                        { self.view_gap_threshold_select(ctx) }
+                                        { self.view_delta_columns_select(ctx) }
+                                    </>
+                                }
+                            } else {
+                                html! {}
+                            }
+                        }
+
+                        { self.view_timezone_select(ctx) }
                     </div>
                 }
             }
+            // The summary and the patterns table show timestamps too.
+            (State::Ready(_), Tab::Information | Tab::Patterns) => html! {
+                <div class="flex grow justify-end">
+                    { self.view_timezone_select(ctx) }
+                </div>
+            },
             _ => html! {},
         }
     }
 
+    pub fn view_gap_threshold_select(&self, ctx: &Context<Self>) -> Html {
+        let selected = match self.display_options.gap_threshold {
+            Some(threshold) => threshold.num_minutes().to_string(),
+            None => "off".to_owned(),
+        };
+
+        let options: Vec<_> = [("off".to_owned(), "No gaps".to_owned())]
+            .into_iter()
+            .chain(GAP_THRESHOLD_OPTIONS.iter().map(|minutes| {
+                (
+                    minutes.to_string(),
+                    format!(
+                        "Gaps ≥ {}",
+                        timeline::format_duration(Duration::minutes(*minutes))
+                    ),
+                )
+            }))
+            .collect();
+
+        html! {
+            <Select
+                classes={classes!("ml-2")}
+                title="Show dividers for gaps between log entries"
+                {options}
+                {selected}
+                on_change={ctx.link().callback(Msg::UpdateGapThreshold)}
+            />
+        }
+    }
+
+    pub fn view_delta_columns_select(&self, ctx: &Context<Self>) -> Html {
+        html! {
+            <Select
+                classes={classes!("ml-2")}
+                title="Show the time since the previous entry and/or since an anchor entry"
+                options={DeltaColumns::iter().map(|columns| (columns.to_string(), columns.to_string())).collect::<Vec<_>>()}
+                selected={self.display_options.delta_columns.to_string()}
+                on_change={ctx.link().callback(Msg::UpdateDeltaColumns)}
+            />
+        }
+    }
+
+    pub fn view_timezone_select(&self, ctx: &Context<Self>) -> Html {
+        html! {
+            <Select
+                classes={classes!("ml-2")}
+                title="Timezone to display timestamps in"
+                options={DisplayTimezone::iter().map(|timezone| (timezone.to_string(), timezone.to_string())).collect::<Vec<_>>()}
+                selected={self.document().timezone.to_string()}
+                on_change={ctx.link().callback(Msg::UpdateTimezone)}
+            />
+        }
+    }
+
+    pub fn view_match_navigation(&self, ctx: &Context<Self>) -> Html {
+        let document = self.document();
+
+        if document.active_query.string.is_empty() {
+            return html! {};
+        }
+
+        let match_count = document.match_count();
+
+        let text = if match_count == 0 {
+            "no matches".to_owned()
+        } else {
+            format!("match {} of {}", document.current_match + 1, match_count)
+        };
+
+        html! {
+            <div class="flex ml-2">
+                <Button
+                    on_click={ctx.link().callback(|_| Msg::PreviousMatch)}
+                    icon={classes!("fas", "fa-chevron-up")}
+                    disabled={match_count == 0}
+                />
+
+                <span class="flex items-center px-2 whitespace-nowrap text-sm border-y border-brand-border dark:border-brand-dark-border">
+                    { text }
+                </span>
+
+                <Button
+                    on_click={ctx.link().callback(|_| Msg::NextMatch)}
+                    icon={classes!("fas", "fa-chevron-down")}
+                    disabled={match_count == 0}
+                />
+            </div>
+        }
+    }
+
+    /// Breadcrumb of the identifiers that the logs were filtered by, which can be gone back to.
+    pub fn view_pivots(&self, ctx: &Context<Self>) -> Html {
+        let pivots = &self.document().pivots;
+
+        if pivots.is_empty() {
+            return html! {};
+        }
+
+        let crumbs = pivots.iter().enumerate().map(|(index, pivot)| {
+            let is_last = index + 1 == pivots.len();
+
+            html! {
+                <>
+                    <Icon icon={classes!("fas", "fa-chevron-right", "text-xs")}/>
+                    <div class="flex">
+                        <Button
+                            classes={classes!("font-mono")}
+                            size={ButtonSize::Small}
+                            text={pivot.identifier.clone()}
+                            active={is_last}
+                            disabled={is_last}
+                            on_click={ctx.link().callback(move |_| Msg::PopPivot(index + 1))}
+                        />
+                    </div>
+                </>
+            }
+        });
+
+        html! {
+            <nav class="flex flex-wrap items-center gap-2 mt-4 text-sm">
+                <Icon icon={classes!("fas", "fa-crosshairs")}/>
+                <div class="flex">
+                    <Button
+                        size={ButtonSize::Small}
+                        text="Before filtering by identifiers"
+                        on_click={ctx.link().callback(|_| Msg::PopPivot(0))}
+                    />
+                </div>
+                { for crumbs }
+            </nav>
+        }
+    }
+
     pub fn view_tab_button(&self, tab: Tab, ctx: &Context<Self>) -> Html {
         html! {
             <Button
                 classes={classes!("grow")}
                 size={ButtonSize::Medium}
                 on_click={ctx.link().callback(move |_| Msg::UpdateTab(tab))}
-                active={self.tab == tab}
+                active={self.document().tab == tab}
                 icon={tab.icon()}
                 text={tab.to_string()}
             />
         }
     }
 
+    pub fn view_entry_details(&self, ctx: &Context<Self>) -> Html {
+        let document = self.document();
+
+        match (&document.state, &document.selection) {
+            (State::Ready(_), Some(selection)) if document.tab.is_logs() => document
+                .active_file()
+                .view_details(selection, document.timezone, ctx.link()),
+            _ => html! {},
+        }
+    }
+
+    pub fn view_shortcuts(&self, ctx: &Context<Self>) -> Html {
+        if !self.show_shortcuts {
+            return html! {};
+        }
+
+        html! {
+            <div
+                class="fixed inset-0 z-20 flex items-center justify-center bg-black/50"
+                onclick={ctx.link().callback(|_| Msg::Shortcut(Shortcut::HideHelp))}
+            >
+                <Message classes={classes!("max-w-lg", "mx-4")} heading="Keyboard shortcuts">
+                    <Table classes={classes!("text-sm")}>
+                        <tbody>
+                            {
+                                for keyboard::SHORTCUTS.iter().map(|(keys, description)| html! {
+                                    <TableRow>
+                                        <TableItem classes={classes!("font-mono", "whitespace-nowrap")}>{ keys }</TableItem>
+                                        <TableItem>{ description }</TableItem>
+                                    </TableRow>
+                                })
+                            }
+                        </tbody>
+                    </Table>
+                </Message>
+            </div>
+        }
+    }
+
     pub fn view_footer(&self) -> Html {
         html! {
             <footer class="bg-brand-bg-footer dark:bg-brand-dark-bg-footer mb-24 px-8 pb-12 pt-6 text-center">
@@ -17,6 +17,12 @@ async function handleRequest(request) {
     const { pathname, searchParams } = new URL(request.url);
     const version = searchParams.has("v") ? searchParams.get("v") : null;
 
+    if (pathname === "/health") {
+        const response = new Response("OK", { status: 200 });
+        setAllowOrigin(response, origin);
+        return response;
+    }
+
     if (pathname.startsWith("/android")) {
         return await respond(origin, pathname, version, "android", "", true);
     }